@@ -25,7 +25,7 @@ impl Bitmask {
     #[inline]
     #[must_use]
     pub fn new(bit_count: u32) -> Self {
-        let mask_count = (bit_count + (BITMASK_BITS - 1)) / BITMASK_BITS;
+        let mask_count = bit_count.div_ceil(BITMASK_BITS);
         Self {
             masks: Box::from_iter((0..mask_count).map(|_| 0)),
             bit_count,
@@ -95,4 +95,35 @@ impl Bitmask {
                 count + mask.count_ones()
             })
     }
+    
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.masks.iter().all(|&mask| mask == 0)
+    }
+    
+    /// Whether any bit is set in both `self` and `other`.
+    #[inline]
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        ::core::iter::zip(
+            self.masks.iter(),
+            other.masks.iter(),
+        ).any(|(lhs, rhs)| lhs & rhs != 0)
+    }
+    
+    /// Whether every bit that is set in `other` is also set in `self`.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, other: &Self) -> bool {
+        ::core::iter::zip(
+            self.masks.iter(),
+            other.masks.iter(),
+        ).all(|(lhs, rhs)| lhs & rhs == *rhs)
+    }
+    
+    /// Iterate over the indices of the bits that are set, in ascending order.
+    pub fn ones(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..self.bit_count).filter(move |&index| self.get_bit(index))
+    }
 }
\ No newline at end of file
@@ -0,0 +1,144 @@
+//! The C header that is generated with `#[vexillo(c_header)]`.
+
+use std::fmt::Write;
+
+use quote::ToTokens;
+use syn::{Ident, ext::IdentExt};
+
+use crate::{const_block::ConstBuildResult, type_def::{Storage, TypeDef}};
+
+/// `ExampleFlags` to `example_flags`.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() && prev_lower {
+            snake.push('_');
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// Builds the header for the flags described by `type_def` and `consts`.
+///
+/// `name` is the base name of the C identifiers. The typedef is `{name}_t`,
+/// the helper functions are `{name}_union` and so on, and the macros are
+/// prefixed with `{NAME}_`. It defaults to the type name in snake case.
+pub(crate) fn build(type_def: &TypeDef, consts: &ConstBuildResult, name: Option<&Ident>) -> syn::Result<String> {
+    let mask_bits = ["u8", "u16", "u32", "u64"]
+        .into_iter()
+        .find(|name| matches!(&type_def.mask_type, syn::Type::Path(path) if path.path.is_ident(name)))
+        .map(|name| name[1..].parse::<u32>().unwrap())
+        .ok_or_else(|| syn::Error::new_spanned(
+            &type_def.mask_type,
+            format!(
+                "`c_header` needs the C type of the masks, so the mask type must be written as `u8`, `u16`, `u32`, or `u64`, not `{}`.",
+                type_def.mask_type.to_token_stream(),
+            ),
+        ))?;
+    let word_count = match type_def.storage {
+        Storage::Array => consts.bit_count().div_ceil(mask_bits) as usize,
+        Storage::Integer => 1,
+    };
+    if word_count == 0 {
+        return Err(syn::Error::new(type_def.type_name.span(), "`c_header` needs at least one flag."));
+    }
+    let type_name = type_def.type_name.unraw().to_string();
+    let name = name.map_or_else(|| snake_case(&type_name), |name| name.unraw().to_string());
+    let prefix = name.to_uppercase();
+    let c_type = format!("{name}_t");
+    let int_type = format!("uint{mask_bits}_t");
+    let int_macro = format!("UINT{mask_bits}_C");
+    let words = |get_bit: &dyn Fn(u32) -> bool| {
+        let mut words = vec![0u64; word_count];
+        for index in (0..consts.bit_count()).filter(|&index| get_bit(index)) {
+            words[(index / mask_bits) as usize] |= 1 << (index % mask_bits);
+        }
+        words
+    };
+    let mut singles = consts.singles.iter().collect::<Vec<_>>();
+    singles.sort_unstable_by_key(|single| single.index);
+    let rows = [
+        ("NONE".to_owned(), words(&|_| false)),
+        ("ALL".to_owned(), words(&|index| singles.iter().any(|single| single.index == index))),
+    ].into_iter()
+        .chain(singles.iter().map(|single| (single.ident.unraw().to_string(), words(&|index| index == single.index))))
+        .chain(consts.groups.iter().zip(consts.group_masks.iter()).map(|(group, mask)| {
+            (group.ident.unraw().to_string(), words(&|index| mask.get_bit(index)))
+        }));
+
+    // `write!` to a `String` can't fail.
+    let mut header = String::new();
+    let guard = format!("{prefix}_H");
+    _=writeln!(header, "/* Generated by vexillo from `{type_name}`. Do not edit. */");
+    _=writeln!(header, "#ifndef {guard}");
+    _=writeln!(header, "#define {guard}");
+    _=writeln!(header);
+    _=writeln!(header, "#include <stdbool.h>");
+    _=writeln!(header, "#include <stdint.h>");
+    _=writeln!(header);
+    let (field, word, loop_start, loop_end, indent) = match type_def.storage {
+        Storage::Array => (
+            format!("{int_type} masks[{word_count}]"),
+            "masks[i]",
+            format!("    for (int i = 0; i < {word_count}; ++i) {{\n"),
+            "    }\n",
+            "        ",
+        ),
+        Storage::Integer => (
+            format!("{int_type} bits"),
+            "bits",
+            String::new(),
+            "",
+            "    ",
+        ),
+    };
+    _=writeln!(header, "typedef struct {c_type} {{");
+    _=writeln!(header, "    {field};");
+    _=writeln!(header, "}} {c_type};");
+    _=writeln!(header);
+    _=writeln!(header, "/* The masks of each flag, by word. `{prefix}_<FLAG>_INIT` initializes a `{c_type}`. */");
+    for (flag, words) in rows {
+        let macro_name = format!("{prefix}_{flag}");
+        match type_def.storage {
+            Storage::Array => {
+                for (i, word) in words.iter().enumerate() {
+                    _=writeln!(header, "#define {macro_name}_{i} {int_macro}({word:#x})");
+                }
+                let init = (0..word_count).map(|i| format!("{macro_name}_{i}")).collect::<Vec<_>>().join(", ");
+                _=writeln!(header, "#define {macro_name}_INIT {{ {{ {init} }} }}");
+            }
+            Storage::Integer => {
+                _=writeln!(header, "#define {macro_name} {int_macro}({:#x})", words[0]);
+                _=writeln!(header, "#define {macro_name}_INIT {{ {macro_name} }}");
+            }
+        }
+    }
+    let mut function = |signature: &str, statement: &str, result: &str| {
+        _=writeln!(header);
+        _=writeln!(header, "static inline {signature} {{");
+        _=write!(header, "{loop_start}{indent}{statement}\n{loop_end}");
+        _=writeln!(header, "    return {result};");
+        _=writeln!(header, "}}");
+    };
+    function(&format!("{c_type} {name}_union({c_type} a, {c_type} b)"), &format!("a.{word} |= b.{word};"), "a");
+    function(&format!("{c_type} {name}_intersection({c_type} a, {c_type} b)"), &format!("a.{word} &= b.{word};"), "a");
+    function(&format!("{c_type} {name}_difference({c_type} a, {c_type} b)"), &format!("a.{word} &= ~b.{word};"), "a");
+    function(&format!("bool {name}_has_all({c_type} a, {c_type} b)"), &format!("if ((a.{word} & b.{word}) != b.{word}) return false;"), "true");
+    function(&format!("bool {name}_has_any({c_type} a, {c_type} b)"), &format!("if ((a.{word} & b.{word}) != 0) return true;"), "false");
+    function(&format!("bool {name}_is_empty({c_type} a)"), &format!("if (a.{word} != 0) return false;"), "true");
+    function(&format!("bool {name}_equals({c_type} a, {c_type} b)"), &format!("if (a.{word} != b.{word}) return false;"), "true");
+    _=writeln!(header);
+    _=writeln!(header, "#endif /* {guard} */");
+    Ok(header)
+}
+
+#[test]
+fn snake_case_test() {
+    assert_eq!(snake_case("Perms"), "perms");
+    assert_eq!(snake_case("ExampleFlags"), "example_flags");
+    assert_eq!(snake_case("Http2Flags"), "http2_flags");
+    assert_eq!(snake_case("IOFlags"), "ioflags");
+}
@@ -1,10 +1,11 @@
-use std::{collections::{HashSet}, sync::LazyLock};
+use std::{collections::{HashMap, HashSet}, sync::LazyLock};
 
 use quote::quote;
+use proc_macro2::Span;
 use syn::{
-    Attribute, Error, Ident, Token, Visibility, braced, bracketed, parse::Parse
+    Attribute, Error, Ident, LitInt, Token, Visibility, braced, bracketed, ext::IdentExt, parse::Parse
 };
-use crate::{override_block::OverrideBlock, vis::Vis};
+use crate::{Errors, bitmask::Bitmask, dep_graph::DepGraph, lint::{AllowedLints, Lint, Warning}, lock::{self, Lockfile}, override_block::OverrideBlock, vis::Vis};
 
 struct AddFlagsItem {
     flags: Vec<Ident>,
@@ -18,34 +19,198 @@ struct DeclareFlagItem {
     attrs: Vec<Attribute>,
     vis: Vis,
     ident: Ident,
+    /// `FLAG = 7` pins the flag to bit 7.
+    pin: Option<LitInt>,
 }
 
 struct DeclareGroupItem {
     attrs: Vec<Attribute>,
+    /// Taken out of the `#[allow(...)]` attributes in `attrs`.
+    allowed: AllowedLints,
     vis: Vis,
     ident: Ident,
     items: Vec<GroupItem>,
 }
 
+/// `reserved 3`, `_`, or `_ = 12`.
+/// 
+/// Reserved bits consume bit indices without declaring a flag.
+struct ReserveItem {
+    span: Span,
+    count: u32,
+    pin: Option<LitInt>,
+}
+
+/// `alias OLD_NAME = NEW_NAME`.
+/// 
+/// Declares a deprecated constant with the value of another flag.
+struct DeclareAliasItem {
+    attrs: Vec<Attribute>,
+    vis: Vis,
+    ident: Ident,
+    target: Ident,
+}
+
+/// `exclusive [A, B, C]`.
+/// 
+/// At most one of the flags may be set.
+struct ExclusiveItem {
+    span: Span,
+    flags: Vec<Ident>,
+}
+
+/// `implies A => B | C`, or `implies A | B => C`.
+/// 
+/// Every flag on the left implies the flags on the right. See `normalize`.
+struct ImpliesItem {
+    span: Span,
+    flags: Vec<Ident>,
+    implied: Vec<Ident>,
+}
+
+mod kw {
+    syn::custom_keyword!(reserved);
+    syn::custom_keyword!(alias);
+    syn::custom_keyword!(exclusive);
+    syn::custom_keyword!(implies);
+}
+
 enum DeclareItem {
     Single(DeclareFlagItem),
     Group(DeclareGroupItem),
+    Reserve(ReserveItem),
+    Alias(DeclareAliasItem),
+    Exclusive(ExclusiveItem),
+    Implies(ImpliesItem),
 }
 
+/// Pinned bit indices must be less than this.
+const MAX_BIT_INDEX: u32 = 65536;
+
 impl Parse for DeclareFlagItem {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let ident = input.parse()?;
+        let pin = if input.peek(Token![=]) {
+            _=input.parse::<Token![=]>()?;
+            let lit = input.parse::<LitInt>()?;
+            if lit.base10_parse::<u32>()? >= MAX_BIT_INDEX {
+                return Err(Error::new(
+                    lit.span(),
+                    format!("Bit index must be less than {MAX_BIT_INDEX}."),
+                ));
+            }
+            Some(lit)
+        } else {
+            None
+        };
         Ok(Self {
-            attrs: input.call(Attribute::parse_outer)?,
-            vis: input.parse()?,
-            ident: input.parse()?,
+            attrs,
+            vis,
+            ident,
+            pin,
         })
     }
 }
 
-impl Parse for DeclareGroupItem {
+impl Parse for ReserveItem {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![_]) {
+            let span = input.parse::<Token![_]>()?.span;
+            let pin = if input.peek(Token![=]) {
+                _=input.parse::<Token![=]>()?;
+                let lit = input.parse::<LitInt>()?;
+                if lit.base10_parse::<u32>()? >= MAX_BIT_INDEX {
+                    return Err(Error::new(
+                        lit.span(),
+                        format!("Bit index must be less than {MAX_BIT_INDEX}."),
+                    ));
+                }
+                Some(lit)
+            } else {
+                None
+            };
+            Ok(Self {
+                span,
+                count: 1,
+                pin,
+            })
+        } else {
+            let span = input.parse::<kw::reserved>()?.span;
+            let lit = input.parse::<LitInt>()?;
+            let count = lit.base10_parse::<u32>()?;
+            if count == 0 || count >= MAX_BIT_INDEX {
+                return Err(Error::new(
+                    lit.span(),
+                    format!("Reserved bit count must be between 1 and {}.", MAX_BIT_INDEX - 1),
+                ));
+            }
+            Ok(Self {
+                span,
+                count,
+                pin: None,
+            })
+        }
+    }
+}
+
+impl Parse for DeclareAliasItem {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let attrs = input.call(Attribute::parse_outer)?;
         let vis = input.parse()?;
+        _=input.parse::<kw::alias>()?;
+        let ident = input.parse()?;
+        _=input.parse::<Token![=]>()?;
+        let target = input.parse()?;
+        Ok(Self {
+            attrs,
+            vis,
+            ident,
+            target,
+        })
+    }
+}
+
+impl Parse for ExclusiveItem {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let span = input.parse::<kw::exclusive>()?.span;
+        let content;
+        bracketed!(content in input);
+        let flags = content.parse_terminated(Ident::parse, Token![,])?
+            .into_iter()
+            .collect::<Vec<_>>();
+        if flags.len() < 2 {
+            return Err(Error::new(span, "An exclusive set needs at least two flags."));
+        }
+        Ok(Self {
+            span,
+            flags,
+        })
+    }
+}
+
+impl Parse for ImpliesItem {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let span = input.parse::<kw::implies>()?.span;
+        let mut flags = Vec::new();
+        read_pipe_separated_idents_into(&input, &mut flags)?;
+        _=input.parse::<Token![=>]>()?;
+        let mut implied = Vec::new();
+        read_pipe_separated_idents_into(&input, &mut implied)?;
+        Ok(Self {
+            span,
+            flags,
+            implied,
+        })
+    }
+}
+
+impl Parse for DeclareGroupItem {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut attrs = input.call(Attribute::parse_outer)?;
+        let allowed = AllowedLints::extract(&mut attrs)?;
+        let vis = input.parse()?;
         let ident = input.parse()?;
         _=input.parse::<Token![:]>()?;
         let content;
@@ -56,6 +221,7 @@ impl Parse for DeclareGroupItem {
         }
         Ok(Self {
             attrs,
+            allowed,
             vis,
             ident,
             items,
@@ -65,9 +231,37 @@ impl Parse for DeclareGroupItem {
 
 impl Parse for DeclareItem {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // `reserved` is only a keyword when followed by a count,
+        // so it can still be used as a flag name.
+        if input.peek(Token![_]) || (input.peek(kw::reserved) && input.peek2(LitInt)) {
+            return Ok(Self::Reserve(input.parse()?));
+        }
+        if input.peek(kw::exclusive) && input.peek2(syn::token::Bracket) {
+            return Ok(Self::Exclusive(input.parse()?));
+        }
+        // `implies` is only a keyword when it starts a rule, so it can still be used as a flag name.
+        if input.peek(kw::implies) && input.peek2(Ident) {
+            let fork = input.fork();
+            _=fork.parse::<kw::implies>()?;
+            let mut flags = Vec::new();
+            read_pipe_separated_idents_into(&&fork, &mut flags)?;
+            if fork.peek(Token![=>]) {
+                return Ok(Self::Implies(input.parse()?));
+            }
+        }
         let fork = input.fork();
         _=fork.call(Attribute::parse_outer)?;
         _=fork.parse::<Vis>()?;
+        // `alias` is only a keyword when followed by `NAME = TARGET`, so `alias` and
+        // `alias NAME = 3` (the flags `alias` and `NAME`) still declare flags.
+        if fork.peek(kw::alias) && fork.peek2(Ident) && fork.peek3(Token![=]) {
+            _=fork.parse::<kw::alias>()?;
+            _=fork.parse::<Ident>()?;
+            _=fork.parse::<Token![=]>()?;
+            if fork.peek(Ident) {
+                return Ok(Self::Alias(input.parse()?));
+            }
+        }
         if fork.peek2(Token![:]) {
             // Group
             Ok(Self::Group(input.parse()?))
@@ -114,11 +308,27 @@ impl DeclareItem {
                     .iter()
                     .try_for_each(move |item| {
                         match item {
+                            GroupItem::Declare(DeclareItem::Alias(alias)) => Err(syn::Error::new(
+                                alias.ident.span(),
+                                "Aliases can only be declared at the top level of the const block.",
+                            )),
+                            GroupItem::Declare(DeclareItem::Exclusive(exclusive)) => Err(syn::Error::new(
+                                exclusive.span,
+                                "Exclusive sets can only be declared at the top level of the const block.",
+                            )),
+                            GroupItem::Declare(DeclareItem::Implies(implies)) => Err(syn::Error::new(
+                                implies.span,
+                                "Implications can only be declared at the top level of the const block.",
+                            )),
                             GroupItem::Declare(declare_item) => declare_item.verify(verifier),
                             _ => Ok(()),
                         }
                     })?;
             },
+            DeclareItem::Alias(item) => {
+                verify_and_insert!(item);
+            },
+            DeclareItem::Reserve(_) | DeclareItem::Exclusive(_) | DeclareItem::Implies(_) => (),
         }
         Ok(())
     }
@@ -165,84 +375,216 @@ pub(crate) struct ConstSingle {
     pub attrs: Vec<Attribute>,
     pub vis: Visibility,
     pub ident: Ident,
+    /// The bit index. Unpinned flags are assigned an index after
+    /// all of the flags have been collected (see `assign_indices`).
     pub index: u32,
+    pub pin: Option<LitInt>,
+    /// The group that the flag is declared in, or `None` at the top level.
+    pub parent: Option<Ident>,
 }
 
 pub(crate) struct ConstGroup {
     pub attrs: Vec<Attribute>,
+    /// Includes the lints allowed on the groups that this group is declared in.
+    pub allowed: AllowedLints,
     pub vis: Visibility,
     pub ident: Ident,
     pub updates: Vec<ConstGroupUpdate>,
+    /// The group that the group is declared in, or `None` at the top level.
+    pub parent: Option<Ident>,
+}
+
+impl ConstSingle {
+    fn new(item: &DeclareFlagItem, parent_vis: &Visibility, parent: Option<&Ident>) -> Self {
+        Self {
+            attrs: item.attrs.clone(),
+            vis: item.vis.resolve(Some(parent_vis)),
+            ident: item.ident.clone(),
+            index: 0,
+            pin: item.pin.clone(),
+            parent: parent.cloned(),
+        }
+    }
+}
+
+/// A deprecated name of a single or group flag.
+pub(crate) struct ConstAlias {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub ident: Ident,
+    pub target: Ident,
+}
+
+impl ConstAlias {
+    fn new(item: &DeclareAliasItem, parent_vis: &Visibility) -> Self {
+        Self {
+            attrs: item.attrs.clone(),
+            vis: item.vis.resolve(Some(parent_vis)),
+            ident: item.ident.clone(),
+            target: item.target.clone(),
+        }
+    }
+}
+
+/// A set of single flags where at most one flag may be set.
+pub(crate) struct ConstExclusive {
+    pub span: Span,
+    pub flags: Vec<Ident>,
+    /// The bit index of each flag, in the same order as `flags`.
+    pub indices: Vec<u32>,
+}
+
+impl ConstExclusive {
+    /// The error for `subject` having more than one flag of the set, if it does.
+    /// `mask` is the flags of `subject`, and the error is at `span`.
+    fn conflict(&self, mask: &Bitmask, span: Span, subject: impl std::fmt::Display) -> Option<syn::Error> {
+        let conflicting = self.flags.iter()
+            .zip(self.indices.iter())
+            .filter(|&(_, &index)| mask.get_bit(index))
+            .map(|(flag, _)| format!("`{flag}`"))
+            .collect::<Vec<_>>();
+        let [rest @ .., last] = conflicting.as_slice() else {
+            return None;
+        };
+        if rest.is_empty() {
+            return None;
+        }
+        let mut err = syn::Error::new(
+            span,
+            format!("{subject} has {} and {last}, which are mutually exclusive.", rest.join(", ")),
+        );
+        err.combine(syn::Error::new(self.span, "The exclusive set is declared here."));
+        Some(err)
+    }
+}
+
+/// The flags that a single flag implies, directly or through other flags.
+pub(crate) struct ConstImplication {
+    pub index: u32,
+    /// The implied single flags. This only has `index` if the rules are cyclic.
+    pub implied: Bitmask,
 }
 
 pub enum ConstGroupUpdate {
     Add(Ident),
     Remove(Ident),
+    /// A flag or group declared inside of the group. This is added like [ConstGroupUpdate::Add].
+    Declare(Ident),
+}
+
+impl ConstGroupUpdate {
+    pub fn ident(&self) -> &Ident {
+        match self {
+            ConstGroupUpdate::Add(ident)
+            | ConstGroupUpdate::Remove(ident)
+            | ConstGroupUpdate::Declare(ident) => ident,
+        }
+    }
+}
+
+/// A declaration that takes up a bit index, in declaration order.
+enum BitDecl {
+    Single(ConstSingle),
+    Reserved {
+        span: Span,
+        pin: Option<LitInt>,
+        index: u32,
+    },
+}
+
+impl BitDecl {
+    fn reserve(bits: &mut Vec<BitDecl>, item: &ReserveItem) {
+        bits.extend((0..item.count).map(|_| BitDecl::Reserved {
+            span: item.span,
+            pin: item.pin.clone(),
+            index: 0,
+        }));
+    }
+    
+    fn pin(&self) -> Option<&LitInt> {
+        match self {
+            BitDecl::Single(single) => single.pin.as_ref(),
+            BitDecl::Reserved { pin, .. } => pin.as_ref(),
+        }
+    }
+    
+    fn index_mut(&mut self) -> &mut u32 {
+        match self {
+            BitDecl::Single(single) => &mut single.index,
+            BitDecl::Reserved { index, .. } => index,
+        }
+    }
+    
+    fn span(&self) -> Span {
+        match self {
+            BitDecl::Single(single) => single.ident.span(),
+            BitDecl::Reserved { span, .. } => *span,
+        }
+    }
+    
+    fn describe(&self) -> String {
+        match self {
+            BitDecl::Single(single) => format!("`{}`", single.ident),
+            BitDecl::Reserved { .. } => String::from("a reserved bit"),
+        }
+    }
 }
 
 struct ConstGroupBuilder {
+    /// The group that is being built.
+    ident: Ident,
     vis: Visibility,
+    allowed: AllowedLints,
     updates: Vec<ConstGroupUpdate>,
-    singles: Vec<ConstSingle>,
+    bits: Vec<BitDecl>,
     groups: Vec<ConstGroup>,
-    index: u32,
 }
 
-struct ConstBlockBuilder {
-    pub single_index: u32,
+struct ConstBlockBuilder<'a> {
     pub vis: Visibility,
-    pub singles: Vec<ConstSingle>,
+    pub bits: Vec<BitDecl>,
     pub groups: Vec<ConstGroup>,
+    pub aliases: Vec<ConstAlias>,
+    pub exclusive: Vec<&'a ExclusiveItem>,
+    pub implies: Vec<&'a ImpliesItem>,
 }
 
-impl ConstBlockBuilder {
+impl ConstBlockBuilder<'_> {
     fn build_single(&mut self, item: &DeclareFlagItem) {
-        let index = self.single_index;
-        self.single_index += 1;
-        self.singles.push(ConstSingle {
-            attrs: item.attrs.clone(),
-            vis: item.vis.resolve(Some(&self.vis)),
-            ident: item.ident.clone(),
-            index,
-        });
+        self.bits.push(BitDecl::Single(ConstSingle::new(item, &self.vis, None)));
     }
     
     fn build_group(&mut self, item: &DeclareGroupItem) {
         let mut builder = ConstGroupBuilder {
+            ident: item.ident.clone(),
             vis: item.vis.resolve(Some(&self.vis)),
+            allowed: item.allowed,
             // additions: Vec::new(),
             // removals: Vec::new(),
             updates: Vec::new(),
-            singles: Vec::new(),
+            bits: Vec::new(),
             groups: Vec::new(),
-            index: self.single_index,
         };
         builder.build_group(item);
-        self.single_index = builder.index;
-        self.singles.extend(builder.singles);
+        self.bits.extend(builder.bits);
         self.groups.extend(builder.groups);
         self.groups.push(ConstGroup {
             attrs: item.attrs.clone(),
+            allowed: builder.allowed,
             vis: builder.vis,
             ident: item.ident.clone(),
             // additions: builder.additions,
             // removals: builder.removals,
             updates: builder.updates,
+            parent: None,
         });
     }
 }
 
 impl ConstGroupBuilder {
     fn build_single(&mut self, item: &DeclareFlagItem) {
-        let index = self.index;
-        self.index += 1;
-        self.singles.push(ConstSingle {
-            attrs: item.attrs.clone(),
-            vis: item.vis.resolve(Some(&self.vis)),
-            ident: item.ident.clone(),
-            index,
-        });
-        self.updates.push(ConstGroupUpdate::Add(item.ident.clone()));
+        self.bits.push(BitDecl::Single(ConstSingle::new(item, &self.vis, Some(&self.ident))));
+        self.updates.push(ConstGroupUpdate::Declare(item.ident.clone()));
     }
     
     fn build_group(&mut self, item: &DeclareGroupItem) {
@@ -261,24 +603,31 @@ impl ConstGroupBuilder {
                         },
                         DeclareItem::Group(group) => {
                             let mut builder = Self {
+                                ident: group.ident.clone(),
                                 vis: group.vis.resolve(Some(&self.vis)),
+                                allowed: group.allowed.union(self.allowed),
                                 updates: Vec::new(),
-                                singles: Vec::new(),
+                                bits: Vec::new(),
                                 groups: Vec::new(),
-                                index: self.index,
                             };
                             builder.build_group(group);
-                            self.index = builder.index;
-                            self.singles.extend(builder.singles);
+                            self.bits.extend(builder.bits);
                             self.groups.extend(builder.groups);
                             self.groups.push(ConstGroup {
                                 attrs: group.attrs.clone(),
+                                allowed: builder.allowed,
                                 vis: builder.vis,
                                 ident: group.ident.clone(),
                                 updates: builder.updates,
+                                parent: Some(self.ident.clone()),
                             });
-                            self.updates.push(ConstGroupUpdate::Add(group.ident.clone()));
+                            self.updates.push(ConstGroupUpdate::Declare(group.ident.clone()));
+                        },
+                        DeclareItem::Reserve(reserve) => {
+                            BitDecl::reserve(&mut self.bits, reserve);
                         },
+                        // Rejected by `DeclareItem::verify`.
+                        DeclareItem::Alias(_) | DeclareItem::Exclusive(_) | DeclareItem::Implies(_) => (),
                     }
                 },
             }
@@ -286,14 +635,16 @@ impl ConstGroupBuilder {
     }
 }
 
-impl ConstBlockBuilder {
+impl ConstBlockBuilder<'_> {
     #[inline]
     fn new(vis: Visibility) -> Self {
         Self {
             vis,
-            singles: Vec::new(),
+            bits: Vec::new(),
             groups: Vec::new(),
-            single_index: 0,
+            aliases: Vec::new(),
+            exclusive: Vec::new(),
+            implies: Vec::new(),
         }
     }
 }
@@ -334,8 +685,14 @@ static RESERVED_CONST_NAMES: LazyLock<HashSet<&'static str>> = LazyLock::new(||
         "NONE",
         // pub
         "ALL",
-        // priv
+        // pub
         "TABLES",
+        // pub
+        "INDEX_SPAN",
+        // pub, with `exclusive [...]`
+        "EXCLUSIVE_SETS",
+        // pub, with `implies ... => ...`
+        "IMPLICATIONS",
     ])
 });
 
@@ -381,7 +738,65 @@ impl<'a> IdentVerifier<'a> {
 
 pub(crate) struct ConstBuildResult {
     pub singles: Vec<ConstSingle>,
+    /// The reserved bit indices. These are not part of any flag.
+    pub reserved: Vec<u32>,
     pub groups: Vec<ConstGroup>,
+    /// The resolved mask of each group, in the same order as `groups`.
+    pub group_masks: Vec<Bitmask>,
+    pub aliases: Vec<ConstAlias>,
+    pub exclusive: Vec<ConstExclusive>,
+    /// The implications of each single flag that implies other flags, ordered by bit index.
+    pub implications: Vec<ConstImplication>,
+    pub warnings: Vec<Warning>,
+}
+
+/// Resolves the names in group updates and rules to the flags that they refer to.
+struct Members<'a> {
+    bit_count: u32,
+    singles: HashMap<&'a Ident, u32>,
+    /// The index of each group in `ConstBuildResult::groups`.
+    groups: HashMap<&'a Ident, usize>,
+    all: Bitmask,
+}
+
+impl<'a> Members<'a> {
+    fn new(consts: &'a ConstBuildResult) -> Self {
+        let bit_count = consts.bit_count();
+        let mut all = Bitmask::new(bit_count);
+        consts.singles.iter().for_each(|single| all.set_bit(single.index, true));
+        Self {
+            bit_count,
+            singles: consts.singles.iter().map(|single| (&single.ident, single.index)).collect(),
+            groups: consts.groups.iter().enumerate().map(|(index, group)| (&group.ident, index)).collect(),
+            all,
+        }
+    }
+    
+    /// The bit index of the single flag `ident`.
+    fn single(&self, ident: &Ident) -> Option<u32> {
+        self.singles.get(ident).copied()
+    }
+    
+    /// The index of the group `ident`.
+    fn group(&self, ident: &Ident) -> Option<usize> {
+        self.groups.get(ident).copied()
+    }
+    
+    /// The flags of `ident`, which is a single flag, a group, or `ALL`. `group_mask` gives
+    /// the mask of a group from its index.
+    /// 
+    /// Undeclared names have already been reported by `ConstBlock::verify`, so they have no flags.
+    fn resolve_member(&self, ident: &Ident, group_mask: impl FnOnce(usize) -> Bitmask) -> Bitmask {
+        if let Some(bit) = self.single(ident) {
+            Bitmask::new_flag(self.bit_count, bit)
+        } else if let Some(group) = self.group(ident) {
+            group_mask(group)
+        } else if ident == "ALL" {
+            self.all.clone()
+        } else {
+            Bitmask::new(self.bit_count)
+        }
+    }
 }
 
 impl ConstBuildResult {
@@ -390,30 +805,33 @@ impl ConstBuildResult {
         let from_index = override_block.get_alt(&from_index).unwrap_or(&from_index);
         let add: Ident = syn::parse_quote!(add);
         let add = override_block.get_alt(&add).unwrap_or(&add);
-        let rem: Ident = syn::parse_quote!(remove);
-        let rem = override_block.get_alt(&rem).unwrap_or(&rem);
         let new: Ident = syn::parse_quote!(new);
         let new = override_block.get_alt(&new).unwrap_or(&new);
         let singles = self.singles
             .iter()
             .map(|single| {
                 // let index = i as u32;
-                let ConstSingle { attrs, vis, ident, index } = single;
+                let ConstSingle { attrs, vis, ident, index, .. } = single;
                 quote!(
                     #(#attrs)*
                     #vis const #ident: Self = Self::#from_index(#index);
                 )
             }).collect::<proc_macro2::TokenStream>();
+        let single_idents = self.singles
+            .iter()
+            .map(|single| (single.index, &single.ident))
+            .collect::<HashMap<_, _>>();
+        // Group masks are resolved by the macro, so the groups are built
+        // from single flags only and never reference each other.
         let groups = self.groups
             .iter()
-            .map(|group| {
-                let ConstGroup { attrs, vis, ident, updates } = group;
-                let updates = updates.iter()
-                    .map(|update| {
-                        match update {
-                            ConstGroupUpdate::Add(ident) => quote!(builder.#add(Self::#ident);),
-                            ConstGroupUpdate::Remove(ident) => quote!(builder.#rem(Self::#ident);),
-                        }
+            .zip(self.group_masks.iter())
+            .map(|(group, mask)| {
+                let ConstGroup { attrs, vis, ident, .. } = group;
+                let updates = mask.ones()
+                    .map(|bit| {
+                        let single = single_idents[&bit];
+                        quote!(builder.#add(Self::#single);)
                     }).collect::<proc_macro2::TokenStream>();
                 quote!(
                     #(#attrs)*
@@ -424,30 +842,552 @@ impl ConstBuildResult {
                     };
                 )
             }).collect::<proc_macro2::TokenStream>();
+        let aliases = self.aliases
+            .iter()
+            .map(|alias| {
+                let ConstAlias { attrs, vis, ident, target } = alias;
+                // A `#[deprecated]` written on the alias replaces the default note.
+                let deprecated = (!attrs.iter().any(|attr| attr.path().is_ident("deprecated"))).then(|| {
+                    let note = format!("Renamed to `{target}`.");
+                    quote!(#[deprecated(note = #note)])
+                });
+                quote!(
+                    #(#attrs)*
+                    #deprecated
+                    #vis const #ident: Self = Self::#target;
+                )
+            }).collect::<proc_macro2::TokenStream>();
         quote!(
             #singles
             #groups
+            #aliases
         )
     }
     
-    // TODO: I'm working out the dependency graph situation for this.
-    // pub fn build_masks<'a>(&'a self) -> HashMap<&'a Ident, Bitmask> {
-    //     unimplemented!()
-    // }
+    /// The number of bits needed to hold every single flag and reserved bit: one past the highest bit index.
+    pub fn bit_count(&self) -> u32 {
+        self.singles.iter()
+            .map(|single| single.index)
+            .chain(self.reserved.iter().copied())
+            .max()
+            .map_or(0, |index| index + 1)
+    }
+    
+    /// Resolves the mask of every group.
+    /// 
+    /// Groups may reference flags that are declared later, so they are resolved
+    /// in dependency order. Within a group, additions are applied before removals.
+    fn build_masks(&self) -> syn::Result<Vec<Bitmask>> {
+        let bit_count = self.bit_count();
+        let members = Members::new(self);
+        let mut graph = DepGraph::new();
+        for group in self.groups.iter() {
+            let mut dependencies = Vec::new();
+            // Unknown identifiers have already been reported by `ConstBlock::verify`.
+            dependencies.extend(
+                group.updates.iter()
+                    .map(ConstGroupUpdate::ident)
+                    .filter(|ident| members.group(ident).is_some())
+            );
+            graph.insert(&group.ident, dependencies);
+        }
+        let order = graph.sort().map_err(|cycles| {
+            cycles.iter().flat_map(|cycle| {
+                let members = cycle.iter()
+                    .map(|ident| format!("`{ident}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                cycle.iter().map(move |ident| syn::Error::new(
+                    ident.span(),
+                    if cycle.len() == 1 {
+                        format!("`{ident}` is a group that references itself.")
+                    } else {
+                        format!("`{ident}` is part of a cyclic group reference between {members}.")
+                    },
+                ))
+            }).reduce(|mut error, next| {
+                error.combine(next);
+                error
+            }).expect("A failed sort has at least one cycle.")
+        })?;
+        let mut masks: Vec<Option<Bitmask>> = vec![None; self.groups.len()];
+        for ident in order {
+            let index = members.group(ident).expect("Only groups are sorted.");
+            let resolve = |ident: &Ident| members.resolve_member(ident, |group| {
+                masks[group].clone().expect("Dependencies are resolved first.")
+            });
+            let mut mask = Bitmask::new(bit_count);
+            let updates = &self.groups[index].updates;
+            for update in updates.iter() {
+                if let ConstGroupUpdate::Add(ident) | ConstGroupUpdate::Declare(ident) = update {
+                    mask.add(&resolve(ident));
+                }
+            }
+            for update in updates.iter() {
+                if let ConstGroupUpdate::Remove(ident) = update {
+                    mask.remove(&resolve(ident));
+                }
+            }
+            masks[index] = Some(mask);
+        }
+        Ok(masks.into_iter().map(|mask| mask.expect("Every group is sorted.")).collect())
+    }
+    
+    /// Reports the groups that have more than one flag of an exclusive set.
+    /// 
+    /// The group masks must be resolved first.
+    fn check_exclusive(&self) -> syn::Result<()> {
+        let mut errors = Errors::default();
+        for (group, mask) in self.groups.iter().zip(self.group_masks.iter()) {
+            for set in self.exclusive.iter() {
+                if let Some(err) = set.conflict(mask, group.ident.span(), format_args!("`{}`", group.ident)) {
+                    errors.push(err);
+                }
+            }
+        }
+        errors.finish(())
+    }
+    
+    /// Resolves the transitive closure of the implication rules.
+    /// 
+    /// The group masks must be resolved first. A flag that implies two flags of an
+    /// exclusive set (including itself) is an error, because its normalized values
+    /// would never be valid.
+    fn build_implications(&self, rules: &[&ImpliesItem]) -> syn::Result<Vec<ConstImplication>> {
+        let bit_count = self.bit_count();
+        let members = Members::new(self);
+        let resolve = |ident: &Ident| members.resolve_member(ident, |group| self.group_masks[group].clone());
+        let mut errors = Errors::default();
+        // The directly implied flags, and the first rule of each flag (for errors).
+        let mut direct: HashMap<u32, (Bitmask, &Ident)> = HashMap::new();
+        for rule in rules {
+            let mut implied = Bitmask::new(bit_count);
+            rule.implied.iter().for_each(|ident| { implied.add(&resolve(ident)); });
+            for flag in rule.flags.iter() {
+                // Undeclared flags have already been reported by `ConstBlock::verify`.
+                let Some(index) = members.single(flag) else {
+                    errors.push(syn::Error::new(
+                        flag.span(),
+                        format!("`{flag}` is a group. Only single flags can imply other flags."),
+                    ));
+                    continue;
+                };
+                direct.entry(index)
+                    .or_insert_with(|| (Bitmask::new(bit_count), flag))
+                    .0
+                    .add(&implied);
+            }
+        }
+        // Expand until nothing changes. Each pass adds at least one flag, so this ends.
+        let mut closure = direct.iter()
+            .map(|(&index, (implied, _))| (index, implied.clone()))
+            .collect::<HashMap<_, _>>();
+        loop {
+            let mut changed = false;
+            for single in self.singles.iter() {
+                let Some(implied) = closure.get(&single.index) else {
+                    continue;
+                };
+                let mut expanded = implied.clone();
+                for bit in implied.ones() {
+                    if let Some(more) = closure.get(&bit) {
+                        expanded.add(more);
+                    }
+                }
+                if &expanded != implied {
+                    closure.insert(single.index, expanded);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        let mut implications = Vec::with_capacity(closure.len());
+        for single in self.singles.iter() {
+            let Some(implied) = closure.remove(&single.index) else {
+                continue;
+            };
+            let flag = direct[&single.index].1;
+            let with_flag = implied.clone().with_bit(single.index);
+            for set in self.exclusive.iter() {
+                let subject = format_args!("With the flags that it implies, `{}`", single.ident);
+                if let Some(err) = set.conflict(&with_flag, flag.span(), subject) {
+                    errors.push(err);
+                }
+            }
+            // `implies A => NONE` implies nothing.
+            if !implied.is_empty() {
+                implications.push(ConstImplication {
+                    index: single.index,
+                    implied,
+                });
+            }
+        }
+        errors.finish(implications)
+    }
+    
+    /// Finds redundant updates, groups with the same flags as an earlier group, and empty groups.
+    /// 
+    /// The group masks must be resolved first.
+    fn lint(&self) -> Vec<Warning> {
+        let bit_count = self.bit_count();
+        let members = Members::new(self);
+        let resolve = |ident: &Ident| members.resolve_member(ident, |group| self.group_masks[group].clone());
+        let mut warnings = Vec::new();
+        let mut first_with_mask: HashMap<&Bitmask, &Ident> = HashMap::new();
+        for (group, mask) in self.groups.iter().zip(self.group_masks.iter()) {
+            let name = &group.ident;
+            if !group.allowed.allows(Lint::Redundant) {
+                let additions = group.updates
+                    .iter()
+                    .filter(|update| !matches!(update, ConstGroupUpdate::Remove(_)))
+                    .map(|update| (update, resolve(update.ident())))
+                    .collect::<Vec<_>>();
+                let mut added = Bitmask::new(bit_count);
+                additions.iter()
+                    .filter(|(update, _)| matches!(update, ConstGroupUpdate::Declare(_)))
+                    .for_each(|(_, mask)| { added.add(mask); });
+                for (i, (update, mask)) in additions.iter().enumerate() {
+                    let ConstGroupUpdate::Add(ident) = update else {
+                        continue;
+                    };
+                    // Covered by the flags added before it, or by a larger addition anywhere in the group.
+                    let covered = added.contains(mask) || additions.iter()
+                        .enumerate()
+                        .any(|(j, (_, other))| i != j && other != mask && other.contains(mask));
+                    if covered {
+                        warnings.push(Warning::new(
+                            Lint::Redundant,
+                            ident.span(),
+                            format!("`+ {ident}` does not add any flags that are not already in `{name}`."),
+                        ));
+                    }
+                    added.add(mask);
+                }
+                for update in group.updates.iter() {
+                    let ConstGroupUpdate::Remove(ident) = update else {
+                        continue;
+                    };
+                    if !added.intersects(&resolve(ident)) {
+                        warnings.push(Warning::new(
+                            Lint::Redundant,
+                            ident.span(),
+                            format!("`- {ident}` does not remove any flags that are in `{name}`."),
+                        ));
+                    }
+                }
+            }
+            if mask.is_empty() {
+                if !group.allowed.allows(Lint::Empty) {
+                    warnings.push(Warning::new(
+                        Lint::Empty,
+                        name.span(),
+                        format!("`{name}` has no flags."),
+                    ));
+                }
+            } else if let Some(first) = first_with_mask.get(mask) {
+                if !group.allowed.allows(Lint::Duplicate) {
+                    warnings.push(Warning::new(
+                        Lint::Duplicate,
+                        name.span(),
+                        format!("`{name}` has the same flags as `{first}`."),
+                    ));
+                }
+            } else {
+                first_with_mask.insert(mask, name);
+            }
+        }
+        warnings
+    }
+}
+
+/// Assigns the bit index of every single flag and reserved bit.
+/// 
+/// Pinned declarations get their pinned index, and single flags in the lockfile get their
+/// recorded index. Then the unpinned declarations get the lowest free indices in declaration
+/// order, after every index in the lockfile. Two declarations at the same index is an error,
+/// and so is a recorded flag that was removed or pinned somewhere else, or a single flag that
+/// is not recorded (unless the lockfile is being updated).
+fn assign_indices(bits: &mut [BitDecl], lock: Option<&Lockfile>) -> syn::Result<()> {
+    let mut taken: HashMap<u32, (&BitDecl, &str)> = HashMap::new();
+    let mut fixed = Vec::with_capacity(bits.len());
+    let mut errors = Errors::default();
+    for decl in bits.iter() {
+        let pin = decl.pin().map(LitInt::base10_parse::<u32>).transpose()?;
+        let locked = match (decl, lock) {
+            (BitDecl::Single(single), Some(lock)) => lock.index_of(&single.ident.unraw().to_string()),
+            _ => None,
+        };
+        let (index, how) = match (pin, locked) {
+            (Some(pin), Some(locked)) if pin != locked => {
+                if let Some(lock) = lock && !lock.update {
+                    errors.push(syn::Error::new(
+                        decl.span(),
+                        format!(
+                            "{} is pinned to bit {pin}, but it is locked to bit {locked} in `{}`. Moving a flag changes the meaning of stored values. Set `{}=1` while building to move it anyway.",
+                            decl.describe(),
+                            lock.display_path,
+                            lock::UPDATE_ENV,
+                        ),
+                    ));
+                }
+                (pin, "pinned")
+            }
+            (Some(pin), _) => (pin, "pinned"),
+            (None, Some(locked)) => (locked, "locked"),
+            (None, None) => {
+                if let (BitDecl::Single(single), Some(lock)) = (decl, lock) && !lock.update {
+                    errors.push(syn::Error::new(
+                        single.ident.span(),
+                        format!(
+                            "`{}` is not recorded in `{}`. Its bit index would change if a flag was added in front of it. Set `{}=1` while building to record it.",
+                            single.ident.unraw(),
+                            lock.display_path,
+                            lock::UPDATE_ENV,
+                        ),
+                    ));
+                }
+                fixed.push(None);
+                continue;
+            }
+        };
+        fixed.push(Some(index));
+        if let Some(&(first, first_how)) = taken.get(&index) {
+            let mut err = syn::Error::new(
+                decl.span(),
+                format!("{} is {how} to bit {index}, which is already used by {}.", decl.describe(), first.describe()),
+            );
+            err.combine(syn::Error::new(
+                first.span(),
+                format!("{} is {first_how} to bit {index} here.", first.describe()),
+            ));
+            errors.push(err);
+        } else {
+            taken.insert(index, (decl, how));
+        }
+    }
+    if let Some(lock) = lock && !lock.update {
+        for (name, index) in lock.entries.iter() {
+            let declared = bits.iter().any(|decl| matches!(decl, BitDecl::Single(single) if single.ident.unraw() == name));
+            if !declared {
+                errors.push(syn::Error::new(
+                    lock.span,
+                    format!(
+                        "`{name}` is locked to bit {index} in `{}`, but it is no longer declared. Removing a flag changes the meaning of stored values. Set `{}=1` while building to remove it anyway.",
+                        lock.display_path,
+                        lock::UPDATE_ENV,
+                    ),
+                ));
+            }
+        }
+    }
+    errors.finish(())?;
+    let taken = taken.into_keys().collect::<HashSet<u32>>();
+    // New flags are appended so that they never reuse the bit of a removed flag.
+    let mut next_free = lock.map_or(0, Lockfile::next_index);
+    for (decl, fixed) in bits.iter_mut().zip(fixed) {
+        let index = match fixed {
+            Some(index) => index,
+            None => {
+                while taken.contains(&next_free) {
+                    next_free += 1;
+                }
+                let index = next_free;
+                next_free += 1;
+                index
+            }
+        };
+        *decl.index_mut() = index;
+    }
+    Ok(())
+}
+
+/// Finds the bit index of every flag in the exclusive sets. Groups can't be members.
+fn resolve_exclusive(items: &[&ExclusiveItem], singles: &[ConstSingle]) -> syn::Result<Vec<ConstExclusive>> {
+    let indices = singles.iter()
+        .map(|single| (&single.ident, single.index))
+        .collect::<HashMap<_, _>>();
+    let mut errors = Errors::default();
+    let mut exclusive = Vec::with_capacity(items.len());
+    for item in items {
+        let mut set = ConstExclusive {
+            span: item.span,
+            flags: item.flags.clone(),
+            indices: Vec::with_capacity(item.flags.len()),
+        };
+        for flag in item.flags.iter() {
+            // Undeclared flags have already been reported by `ConstBlock::verify`.
+            match indices.get(flag) {
+                Some(&index) => set.indices.push(index),
+                None => errors.push(syn::Error::new(
+                    flag.span(),
+                    format!("`{flag}` is a group. Exclusive sets can only contain single flags."),
+                )),
+            }
+        }
+        exclusive.push(set);
+    }
+    errors.finish(exclusive)
+}
+
+/// The Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitute = diagonal + usize::from(a_char != b_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitute.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Find the closest name to `name` within an edit distance of a third of its length (at least 1).
+fn suggest<'a, It: IntoIterator<Item = &'a str>>(name: &str, candidates: It) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates.into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|&(distance, _)| distance <= max_distance)
+        // `min_by_key` keeps the first of equally close candidates.
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+impl DeclareItem {
+    /// Visit the identifiers in the `+`/`-` updates of this item and the items nested in it.
+    fn for_each_update_ident<'a, F: FnMut(&'a Ident)>(&'a self, f: &mut F) {
+        if let DeclareItem::Group(group) = self {
+            for item in group.items.iter() {
+                match item {
+                    GroupItem::Add(AddFlagsItem { flags }) | GroupItem::Remove(RemoveFlagsItem { flags }) => {
+                        flags.iter().for_each(&mut *f);
+                    },
+                    GroupItem::Declare(declare) => declare.for_each_update_ident(f),
+                }
+            }
+        }
+    }
 }
 
 impl ConstBlock {
+    /// The target of the alias `ident`, if `ident` is an alias.
+    fn alias_target(&self, ident: &Ident) -> Option<&Ident> {
+        self.items.iter().find_map(|item| match item {
+            DeclareItem::Alias(alias) if alias.ident == *ident => Some(&alias.target),
+            _ => None,
+        })
+    }
+    
     fn verify(self) -> syn::Result<Self> {
         let mut verifier = IdentVerifier::new();
-        let verifier = &mut verifier;
         // Check for repeat declarations.
-        self.items.iter().try_for_each(move |item| {
-            item.verify(verifier)
+        self.items.iter().try_for_each(|item| {
+            item.verify(&mut verifier)
         })?;
-        Ok(self)
+        let alias_targets = self.items.iter()
+            .filter_map(|item| match item {
+                DeclareItem::Alias(alias) => Some((&alias.ident, &alias.target)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+        // Check that every flag in a group update is declared.
+        let mut declared = verifier.declared.iter()
+            .filter(|ident| !alias_targets.contains_key(*ident))
+            .map(|ident| ident.to_string())
+            .collect::<Vec<_>>();
+        // Sorted so that suggestions do not depend on hash order.
+        declared.sort_unstable();
+        let targets = declared.clone();
+        declared.extend(["ALL", "NONE"].map(String::from));
+        let mut errors = Errors::default();
+        let not_declared = |ident: &Ident, candidates: &[String]| {
+            let name = ident.to_string();
+            let message = match suggest(&name, candidates.iter().map(String::as_str)) {
+                Some(suggestion) => format!("`{name}` is not a declared flag. Did you mean `{suggestion}`?"),
+                None => format!("`{name}` is not a declared flag."),
+            };
+            syn::Error::new(ident.span(), message)
+        };
+        self.items.iter().for_each(|item| item.for_each_update_ident(&mut |ident: &Ident| {
+            if let Some(target) = alias_targets.get(ident) {
+                errors.push(syn::Error::new(
+                    ident.span(),
+                    format!("`{ident}` is a deprecated alias of `{target}`. Use `{target}` instead."),
+                ));
+            } else if verifier.get(ident).is_none() && ident != "ALL" && ident != "NONE" {
+                errors.push(not_declared(ident, &declared));
+            }
+        }));
+        // Check that every alias names a flag. Aliases of aliases would only
+        // be deprecated twice, so they must name the flag directly.
+        let aliases = self.items.iter().filter_map(|item| match item {
+            DeclareItem::Alias(alias) => Some((&alias.ident, &alias.target)),
+            _ => None,
+        });
+        for (alias, target) in aliases {
+            if let Some(next) = self.alias_target(target) {
+                errors.push(syn::Error::new(
+                    target.span(),
+                    format!("`{target}` is an alias of `{next}`, so `{alias}` must be an alias of `{next}`."),
+                ));
+            } else if verifier.get(target).is_none() {
+                errors.push(not_declared(target, &targets));
+            }
+        }
+        // Check that every flag in an implication is declared.
+        let rules = self.items.iter().filter_map(|item| match item {
+            DeclareItem::Implies(implies) => Some(implies),
+            _ => None,
+        });
+        for rule in rules {
+            let idents = rule.flags.iter()
+                .map(|flag| (flag, false))
+                .chain(rule.implied.iter().map(|flag| (flag, true)));
+            for (flag, implied) in idents {
+                if let Some(target) = alias_targets.get(flag) {
+                    errors.push(syn::Error::new(
+                        flag.span(),
+                        format!("`{flag}` is a deprecated alias of `{target}`. Use `{target}` instead."),
+                    ));
+                } else if implied && (flag == "ALL" || flag == "NONE") {
+                    continue;
+                } else if verifier.get(flag).is_none() {
+                    errors.push(not_declared(flag, if implied { &declared } else { &targets }));
+                }
+            }
+        }
+        // Check that every flag in an exclusive set is declared once.
+        let exclusive = self.items.iter().filter_map(|item| match item {
+            DeclareItem::Exclusive(exclusive) => Some(exclusive),
+            _ => None,
+        });
+        for set in exclusive {
+            for (i, flag) in set.flags.iter().enumerate() {
+                if let Some(target) = alias_targets.get(flag) {
+                    errors.push(syn::Error::new(
+                        flag.span(),
+                        format!("`{flag}` is a deprecated alias of `{target}`. Use `{target}` instead."),
+                    ));
+                } else if verifier.get(flag).is_none() {
+                    errors.push(not_declared(flag, &targets));
+                } else if set.flags[..i].contains(flag) {
+                    errors.push(syn::Error::new(
+                        flag.span(),
+                        format!("`{flag}` is in the exclusive set more than once."),
+                    ));
+                }
+            }
+        }
+        errors.finish(self)
     }
     
-    pub fn build(&self) -> ConstBuildResult {
+    /// Resolve the flags. `lock` is the lockfile that records the bit indices, if there is one.
+    pub fn build(&self, lock: Option<&Lockfile>) -> syn::Result<ConstBuildResult> {
         let mut builder = ConstBlockBuilder::new(self.vis.resolve(None));
         for item in self.items.iter() {
             match item {
@@ -457,12 +1397,48 @@ impl ConstBlock {
                 DeclareItem::Group(group) => {
                     builder.build_group(group);
                 },
+                DeclareItem::Reserve(reserve) => {
+                    BitDecl::reserve(&mut builder.bits, reserve);
+                },
+                DeclareItem::Alias(alias) => {
+                    builder.aliases.push(ConstAlias::new(alias, &builder.vis));
+                },
+                DeclareItem::Exclusive(exclusive) => {
+                    builder.exclusive.push(exclusive);
+                },
+                DeclareItem::Implies(implies) => {
+                    builder.implies.push(implies);
+                },
             }
         }
-        ConstBuildResult {
-            singles: builder.singles,
-            groups: builder.groups,
+        let mut bits = builder.bits;
+        assign_indices(&mut bits, lock)?;
+        let mut singles = Vec::with_capacity(bits.len());
+        let mut reserved = Vec::new();
+        for decl in bits {
+            match decl {
+                BitDecl::Single(single) => singles.push(single),
+                BitDecl::Reserved { index, .. } => reserved.push(index),
+            }
         }
+        // Single flags are ordered by bit index from here on.
+        singles.sort_by_key(|single| single.index);
+        let exclusive = resolve_exclusive(&builder.exclusive, &singles)?;
+        let mut result = ConstBuildResult {
+            singles,
+            reserved,
+            groups: builder.groups,
+            group_masks: Vec::new(),
+            aliases: builder.aliases,
+            exclusive,
+            implications: Vec::new(),
+            warnings: Vec::new(),
+        };
+        result.group_masks = result.build_masks()?;
+        result.check_exclusive()?;
+        result.implications = result.build_implications(&builder.implies)?;
+        result.warnings = result.lint();
+        Ok(result)
     }
 }
 
@@ -505,3 +1481,215 @@ impl Parse for ConstBlock {
 //         todo!()
 //     }
 // }
+
+#[test]
+fn unknown_update_ident_test() {
+    let Err(err) = syn::parse_str::<ConstBlock>("pub const {
+        BAN_USER
+        UNBAN_USER
+        MODS: [+BAN_USR -UNBAN_USER +GUESTS]
+        GUEST: [+LOBBY_ACCESS]
+        LOBBY_ACCESS
+    }") else {
+        panic!("Unknown identifiers were not reported.");
+    };
+    let messages = err.into_iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(messages, [
+        "`BAN_USR` is not a declared flag. Did you mean `BAN_USER`?",
+        "`GUESTS` is not a declared flag. Did you mean `GUEST`?",
+    ]);
+}
+
+#[test]
+fn alias_test() {
+    let Err(err) = syn::parse_str::<ConstBlock>("pub const {
+        REMOVE_MEMBER
+        alias KICK = REMOVE_MEMBER
+        alias BOOT = KICK
+        alias BAN = REMOVE_MEMBR
+        MODS: [+KICK]
+    }") else {
+        panic!("Invalid aliases were not reported.");
+    };
+    let messages = err.into_iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(messages, [
+        "`KICK` is a deprecated alias of `REMOVE_MEMBER`. Use `REMOVE_MEMBER` instead.",
+        "`KICK` is an alias of `REMOVE_MEMBER`, so `BOOT` must be an alias of `REMOVE_MEMBER`.",
+        "`REMOVE_MEMBR` is not a declared flag. Did you mean `REMOVE_MEMBER`?",
+    ]);
+    // `alias` is still a flag name unless it is followed by `NAME = TARGET`.
+    let consts = syn::parse_str::<ConstBlock>("pub const {
+        alias NAME = 3
+        OTHER
+    }").unwrap().build(None).unwrap();
+    let names = consts.singles.iter().map(|single| single.ident.to_string()).collect::<Vec<_>>();
+    assert_eq!(names, ["alias", "OTHER", "NAME"]);
+}
+
+#[test]
+fn exclusive_test() {
+    let Err(err) = syn::parse_str::<ConstBlock>("pub const {
+        PUBLIC
+        PRIVATE
+        EDIT
+        BOTH: [+PUBLIC | PRIVATE]
+        exclusive [PUBLIC, PRIVATE]
+        exclusive [EDIT, BOTH]
+    }").unwrap().build(None) else {
+        panic!("A group in an exclusive set was not reported.");
+    };
+    assert_eq!(err.to_string(), "`BOTH` is a group. Exclusive sets can only contain single flags.");
+    let Err(err) = syn::parse_str::<ConstBlock>("pub const {
+        PUBLIC
+        PRIVATE
+        EDIT
+        BOTH: [+PUBLIC | EDIT | PRIVATE]
+        exclusive [PUBLIC, PRIVATE]
+        exclusive [PUBLIC, EDIT, PRIVATE]
+    }").unwrap().build(None) else {
+        panic!("Groups that break an exclusive set were not reported.");
+    };
+    let messages = err.into_iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(messages, [
+        "`BOTH` has `PUBLIC` and `PRIVATE`, which are mutually exclusive.",
+        "The exclusive set is declared here.",
+        "`BOTH` has `PUBLIC`, `EDIT` and `PRIVATE`, which are mutually exclusive.",
+        "The exclusive set is declared here.",
+    ]);
+}
+
+#[test]
+fn implies_test() {
+    let consts = syn::parse_str::<ConstBlock>("pub const {
+        A
+        B
+        C
+        AB: [+A | B]
+        implies A => C
+        implies C => AB
+        implies B => NONE
+    }").unwrap().build(None).unwrap();
+    let implications = consts.implications.iter()
+        .map(|implication| (implication.index, implication.implied.ones().collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+    // `C` implies `A`, which implies `C`, so `C` is part of its own implications.
+    assert_eq!(implications, [(0, vec![0, 1, 2]), (2, vec![0, 1, 2])]);
+    // `implies` is still a flag name unless it starts a rule.
+    let consts = syn::parse_str::<ConstBlock>("pub const {
+        implies
+        A
+    }").unwrap().build(None).unwrap();
+    assert_eq!(consts.singles.len(), 2);
+    let Err(err) = syn::parse_str::<ConstBlock>("pub const {
+        PUBLIC
+        PRIVATE
+        SHARE
+        implies SHARE => PUBLIC
+        implies PRIVATE => SHARE
+        exclusive [PUBLIC, PRIVATE]
+    }").unwrap().build(None) else {
+        panic!("An implication that breaks an exclusive set was not reported.");
+    };
+    let messages = err.into_iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(messages, [
+        "With the flags that it implies, `PRIVATE` has `PUBLIC` and `PRIVATE`, which are mutually exclusive.",
+        "The exclusive set is declared here.",
+    ]);
+}
+
+#[test]
+fn edit_distance_test() {
+    assert_eq!(edit_distance("", "ABC"), 3);
+    assert_eq!(edit_distance("BAN_USER", "BAN_USER"), 0);
+    assert_eq!(edit_distance("BAN_USR", "BAN_USER"), 1);
+    assert_eq!(edit_distance("KITTEN", "SITTING"), 3);
+    assert_eq!(suggest("ZZZZ", ["BAN_USER", "UNBAN_USER"]), None);
+}
+
+#[test]
+fn lint_test() {
+    let consts = syn::parse_str::<ConstBlock>("pub const {
+        A
+        B
+        C
+        AB: [+A | B]
+        REDUNDANT: [
+            +AB
+            +A
+            -C
+            NESTED: [+B]
+        ]
+        SAME: [+B | A]
+        EMPTY: [+A -A]
+        #[allow(vexillo::redundant, vexillo::duplicate, vexillo::empty)]
+        ALLOWED: [
+            +A | A
+            -C
+            INHERITED: [+A -A]
+        ]
+    }").unwrap().build(None).unwrap();
+    let messages = consts.warnings.iter().map(|warning| warning.message.as_str()).collect::<Vec<_>>();
+    assert_eq!(messages, [
+        "`+ A` does not add any flags that are not already in `REDUNDANT`.",
+        "`- C` does not remove any flags that are in `REDUNDANT`.",
+        "`REDUNDANT` has the same flags as `AB`.",
+        "`SAME` has the same flags as `AB`.",
+        "`EMPTY` has no flags.",
+    ]);
+    let mut attrs: Vec<Attribute> = vec![syn::parse_quote!(#[allow(vexillo::empty, dead_code)])];
+    let allowed = AllowedLints::extract(&mut attrs).unwrap();
+    assert!(allowed.allows(Lint::Empty) && !allowed.allows(Lint::Redundant));
+    assert_eq!(attrs.len(), 1);
+    assert_eq!(quote!(#(#attrs)*).to_string(), quote!(#[allow(dead_code)]).to_string());
+    let mut attrs: Vec<Attribute> = vec![syn::parse_quote!(#[allow(vexillo::redundnat)])];
+    assert!(AllowedLints::extract(&mut attrs).is_err());
+}
+
+#[test]
+fn lock_test() {
+    let lock = |update: bool| Lockfile {
+        display_path: String::from("perms.lock"),
+        path: std::path::PathBuf::from("perms.lock"),
+        span: Span::call_site(),
+        entries: vec![(String::from("KICK"), 0), (String::from("BAN_USER"), 3)],
+        text: None,
+        update,
+    };
+    let block = syn::parse_str::<ConstBlock>("pub const {
+        BAN_USER
+        SEND_MESSAGES
+        KICK
+    }").unwrap();
+    // A new flag would move if another flag was added in front of it before it was recorded.
+    let Err(err) = block.build(Some(&lock(false))) else {
+        panic!("The unrecorded flag was not reported.");
+    };
+    assert_eq!(
+        err.to_string(),
+        "`SEND_MESSAGES` is not recorded in `perms.lock`. Its bit index would change if a flag was added in front of it. Set `VEXILLO_UPDATE_LOCK=1` while building to record it.",
+    );
+    let consts = block.build(Some(&lock(true))).unwrap();
+    let indices = consts.singles.iter()
+        .map(|single| (single.ident.to_string(), single.index))
+        .collect::<Vec<_>>();
+    assert_eq!(indices, [("KICK".into(), 0), ("BAN_USER".into(), 3), ("SEND_MESSAGES".into(), 4)]);
+
+    let block = syn::parse_str::<ConstBlock>("pub const {
+        BAN_USER = 1
+        SEND_MESSAGES
+    }").unwrap();
+    let Err(err) = block.build(Some(&lock(false))) else {
+        panic!("Moved and removed flags were not reported.");
+    };
+    let messages = err.into_iter().map(|err| err.to_string()).collect::<Vec<_>>();
+    assert_eq!(messages, [
+        "`BAN_USER` is pinned to bit 1, but it is locked to bit 3 in `perms.lock`. Moving a flag changes the meaning of stored values. Set `VEXILLO_UPDATE_LOCK=1` while building to move it anyway.",
+        "`SEND_MESSAGES` is not recorded in `perms.lock`. Its bit index would change if a flag was added in front of it. Set `VEXILLO_UPDATE_LOCK=1` while building to record it.",
+        "`KICK` is locked to bit 0 in `perms.lock`, but it is no longer declared. Removing a flag changes the meaning of stored values. Set `VEXILLO_UPDATE_LOCK=1` while building to remove it anyway.",
+    ]);
+    let consts = block.build(Some(&lock(true))).unwrap();
+    let indices = consts.singles.iter()
+        .map(|single| (single.ident.to_string(), single.index))
+        .collect::<Vec<_>>();
+    assert_eq!(indices, [("BAN_USER".into(), 1), ("SEND_MESSAGES".into(), 4)]);
+}
@@ -1,90 +1,156 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{HashMap, VecDeque};
 
 use syn::Ident;
 
 
 /// Dependency Graph Node.
-/// Contains a set of dependencies, and a set of
-/// dependents, representing edges in a dependency
+/// Contains the indices of the node's dependencies
+/// and dependents, representing edges in a dependency
 /// graph.
-#[derive(Default)]
 struct DepNode<'a> {
-    dependencies: HashSet<&'a Ident>,
-    dependents: HashSet<&'a Ident>,
+    ident: &'a Ident,
+    dependencies: Vec<usize>,
+    dependents: Vec<usize>,
 }
 
+/// A dependency graph of identifiers.
+///
+/// Nodes are kept in insertion order so that the sort order
+/// and the reported cycles are deterministic.
+#[derive(Default)]
 pub struct DepGraph<'a> {
-    nodes: HashMap<&'a Ident, HashSet<&'a Ident>>,
+    indices: HashMap<&'a Ident, usize>,
+    nodes: Vec<DepNode<'a>>,
 }
 
 impl<'a> DepGraph<'a> {
     #[inline]
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            nodes: HashMap::new(),
-        }
+        Self::default()
     }
-    
-    pub fn insert<It: IntoIterator<Item = &'a Ident>>(&mut self, ident: &'a Ident, dependencies: It) {
-        let node_mut = self.nodes.entry(ident).or_insert_with(HashSet::new);
-        node_mut.extend(dependencies);
+
+    fn node(&mut self, ident: &'a Ident) -> usize {
+        *self.indices.entry(ident).or_insert_with(|| {
+            self.nodes.push(DepNode {
+                ident,
+                dependencies: Vec::new(),
+                dependents: Vec::new(),
+            });
+            self.nodes.len() - 1
+        })
     }
-    
-    pub fn sort(&self) -> Result<Vec<&'a Ident>, ()> {
-        // By this point, the dependency graph has only been built with
-        // nodes that have their edges defined by dependencies, but not
-        // dependents.
-        // This topological sort algorithm depends on knowing a node's
-        // dependencies AND dependents.
-        // A new graph needs to be rebuilt, by stepping through the
-        // pre-built graph and iterating over each node(N)'s dependencies,
-        // and inserting node(n) into the dependency node's dependents set.
-        let mut full_graph = HashMap::new();
-        for (&key, dependencies) in self.nodes.iter() {
-            full_graph.entry(key).or_insert_with(|| DepNode {
-                dependencies: HashSet::with_capacity(dependencies.len()),
-                dependents: HashSet::new(),
-            }).dependencies.extend(dependencies.iter().copied());
-            for dep in dependencies.iter().copied() {
-                let dep_node = full_graph.entry(dep).or_insert_with(|| DepNode {
-                    dependencies: HashSet::new(),
-                    dependents: HashSet::new(),
-                });
-                dep_node.dependents.insert(key);
+
+    /// Insert `ident` with its `dependencies`. Dependencies that have not been
+    /// inserted yet are added as nodes without dependencies of their own.
+    pub fn insert<It: IntoIterator<Item = &'a Ident>>(&mut self, ident: &'a Ident, dependencies: It) {
+        let index = self.node(ident);
+        for dep in dependencies {
+            let dep = self.node(dep);
+            if !self.nodes[index].dependencies.contains(&dep) {
+                self.nodes[index].dependencies.push(dep);
+                self.nodes[dep].dependents.push(index);
             }
         }
-        let mut order = Vec::with_capacity(full_graph.len());
-        let mut queue = VecDeque::with_capacity(full_graph.len());
-        let mut in_degrees = HashMap::with_capacity(full_graph.len());
-        let mut total_degrees = 0usize;
-        for (&key, node) in full_graph.iter() {
-            if node.dependencies.len() == 0 {
-                queue.push_back(key);
-            } else {
-                let node_dep_count = node.dependencies.len();
-                total_degrees += node_dep_count;
-                in_degrees.insert(key, node_dep_count);
+    }
+
+    /// Sort the nodes so that every node comes after its dependencies.
+    ///
+    /// If the graph has cycles, the cycles are returned instead. Each cycle
+    /// holds every node that takes part in it, in insertion order.
+    pub fn sort(&self) -> Result<Vec<&'a Ident>, Vec<Vec<&'a Ident>>> {
+        let mut order = Vec::with_capacity(self.nodes.len());
+        let mut in_degrees = self.nodes
+            .iter()
+            .map(|node| node.dependencies.len())
+            .collect::<Vec<_>>();
+        let mut queue = (0..self.nodes.len())
+            .filter(|&index| in_degrees[index] == 0)
+            .collect::<VecDeque<_>>();
+        while let Some(next) = queue.pop_front() {
+            order.push(self.nodes[next].ident);
+            for &dependent in self.nodes[next].dependents.iter() {
+                in_degrees[dependent] -= 1;
+                if in_degrees[dependent] == 0 {
+                    queue.push_back(dependent);
+                }
             }
         }
-        
-        while let Some(next_key) = queue.pop_front() {
-            order.push(next_key);
-            for dep_node in full_graph[next_key].dependents.iter().copied() {
-                let Some(degrees) = in_degrees.get_mut(dep_node) else {
-                    unreachable!("in_degrees should always be valid.");
-                };
-                *degrees -= 1;
-                total_degrees -= 1;
-                if *degrees == 0 {
-                    queue.push_back(dep_node);
+        if order.len() == self.nodes.len() {
+            Ok(order)
+        } else {
+            Err(self.cycles())
+        }
+    }
+
+    /// Find the strongly connected components that form cycles (Tarjan's algorithm).
+    fn cycles(&self) -> Vec<Vec<&'a Ident>> {
+        struct Tarjan<'g, 'a> {
+            graph: &'g DepGraph<'a>,
+            next_index: usize,
+            indices: Vec<Option<usize>>,
+            low_links: Vec<usize>,
+            stack: Vec<usize>,
+            on_stack: Vec<bool>,
+            cycles: Vec<Vec<usize>>,
+        }
+        impl Tarjan<'_, '_> {
+            fn visit(&mut self, node: usize) {
+                self.indices[node] = Some(self.next_index);
+                self.low_links[node] = self.next_index;
+                self.next_index += 1;
+                self.stack.push(node);
+                self.on_stack[node] = true;
+                for &dep in self.graph.nodes[node].dependencies.iter() {
+                    match self.indices[dep] {
+                        None => {
+                            self.visit(dep);
+                            self.low_links[node] = self.low_links[node].min(self.low_links[dep]);
+                        }
+                        Some(dep_index) if self.on_stack[dep] => {
+                            self.low_links[node] = self.low_links[node].min(dep_index);
+                        }
+                        Some(_) => (),
+                    }
+                }
+                if Some(self.low_links[node]) != self.indices[node] {
+                    return;
+                }
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                // A single node is only a cycle if it depends on itself.
+                if component.len() > 1 || self.graph.nodes[node].dependencies.contains(&node) {
+                    component.sort_unstable();
+                    self.cycles.push(component);
                 }
             }
         }
-        if total_degrees > 0 {
-            return Err(());
+        let len = self.nodes.len();
+        let mut tarjan = Tarjan {
+            graph: self,
+            next_index: 0,
+            indices: vec![None; len],
+            low_links: vec![0; len],
+            stack: Vec::new(),
+            on_stack: vec![false; len],
+            cycles: Vec::new(),
+        };
+        for node in 0..len {
+            if tarjan.indices[node].is_none() {
+                tarjan.visit(node);
+            }
         }
-        Ok(order)
+        let mut cycles = tarjan.cycles;
+        cycles.sort_unstable_by_key(|cycle| cycle[0]);
+        cycles.into_iter()
+            .map(|cycle| cycle.into_iter().map(|node| self.nodes[node].ident).collect())
+            .collect()
     }
 }
 
@@ -106,6 +172,7 @@ fn dep_graph_test() {
     //     };
     // }
     let mut graph = DepGraph::new();
+    graph.insert(&i[7], [&i[6]]);
     graph.insert(&i[0], []);
     graph.insert(&i[1], []);
     graph.insert(&i[2], [&i[1]]);
@@ -113,19 +180,38 @@ fn dep_graph_test() {
     graph.insert(&i[4], [&i[3]]);
     graph.insert(&i[5], [&i[4]]);
     graph.insert(&i[6], [&i[5]]);
-    graph.insert(&i[7], [&i[6]]);
     if let Ok(sort) = graph.sort() {
         assert_eq!(sort.len(), 8);
-        for node in sort {
-            println!("{node}");
+        let position = |ident: &Ident| sort.iter().position(|&node| node == ident).unwrap();
+        for pair in i[1..].windows(2) {
+            assert!(position(&pair[0]) < position(&pair[1]));
         }
     } else {
         panic!("Cycle detected.");
     }
 }
 
-/*
-ident: additions: [ident], removals: [ident]
-ident: mask
-
-*/
\ No newline at end of file
+#[test]
+fn dep_graph_cycles_test() {
+    let i: [Ident; 6] = [
+        syn::parse_quote!(i0),
+        syn::parse_quote!(i1),
+        syn::parse_quote!(i2),
+        syn::parse_quote!(i3),
+        syn::parse_quote!(i4),
+        syn::parse_quote!(i5),
+    ];
+    let mut graph = DepGraph::new();
+    // i0 -> i1 -> i2 -> i0
+    graph.insert(&i[0], [&i[1]]);
+    graph.insert(&i[1], [&i[2]]);
+    graph.insert(&i[2], [&i[0]]);
+    // i3 depends on a cycle, but is not part of one.
+    graph.insert(&i[3], [&i[0]]);
+    // i4 depends on itself.
+    graph.insert(&i[4], [&i[4], &i[5]]);
+    let Err(cycles) = graph.sort() else {
+        panic!("Cycles not detected.");
+    };
+    assert_eq!(cycles, [vec![&i[0], &i[1], &i[2]], vec![&i[4]]]);
+}
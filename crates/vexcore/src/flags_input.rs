@@ -1,7 +1,9 @@
-use quote::{quote, ToTokens};
-use syn::{Ident, Path, Token, parse::Parse, visit_mut::VisitMut};
+use std::collections::HashMap;
 
-use crate::{const_block::{ConstBlock, ConstBuildResult}, override_block::{OverrideBlock, OverrideStage, Overrider}, type_def::TypeDef};
+use quote::{format_ident, quote, ToTokens};
+use syn::{Ident, Path, Token, ext::IdentExt, parse::Parse, visit_mut::VisitMut};
+
+use crate::{Errors, c_header, lock::{self, Lockfile}, const_block::{ConstBlock, ConstBuildResult, ConstGroupUpdate, ConstSingle}, options::{DebugStyle, DisplayStyle, FlagsOptions, SerdeRepr, SerdeUnknown}, override_block::{OverrideBlock, OverrideStage, Overrider}, type_def::{Storage, TypeDef}};
 
 pub struct FlagsInput {
     // The root vexillo crate path must be known to the
@@ -10,8 +12,62 @@ pub struct FlagsInput {
     // That allows the user to write `flags!(...)` instead of `flags!(use path_to_vexillo; ...)`
     pub(crate) vexillo_crate: Path,
     pub(crate) type_def: TypeDef,
+    pub(crate) options: FlagsOptions,
     pub(crate) config: OverrideBlock,
     pub(crate) consts: ConstBuildResult,
+    pub(crate) flag_enum: Option<FlagEnum>,
+    pub(crate) c_header: Option<String>,
+    pub(crate) lock_path: Option<std::path::PathBuf>,
+}
+
+/// The enum with a variant for each single flag, requested with `#[vexillo(flag_enum)]`.
+pub(crate) struct FlagEnum {
+    pub name: Ident,
+    /// The variant of each single flag, in the same order as `ConstBuildResult::singles`.
+    pub variants: Vec<Ident>,
+}
+
+impl FlagEnum {
+    fn new(name: Ident, singles: &[ConstSingle]) -> syn::Result<Self> {
+        if singles.is_empty() {
+            return Err(syn::Error::new(name.span(), format!("`{name}` would have no variants because there are no single flags.")));
+        }
+        let mut errors = Errors::default();
+        let mut taken = HashMap::<String, &Ident>::new();
+        let mut variants = Vec::with_capacity(singles.len());
+        for single in singles {
+            let variant = upper_camel_case(&single.ident.unraw().to_string());
+            if !variant.starts_with(|c: char| c.is_alphabetic()) {
+                errors.push(syn::Error::new(
+                    single.ident.span(),
+                    format!("`{}` can not be turned into a variant of `{name}`.", single.ident),
+                ));
+                continue;
+            }
+            if let Some(other) = taken.insert(variant.clone(), &single.ident) {
+                errors.push(syn::Error::new(
+                    single.ident.span(),
+                    format!("`{}` and `{other}` would both be the variant `{variant}` of `{name}`.", single.ident),
+                ));
+                continue;
+            }
+            variants.push(Ident::new(&variant, single.ident.span()));
+        }
+        errors.finish(Self { name, variants })
+    }
+}
+
+/// `BAN_USER` to `BanUser`.
+fn upper_camel_case(name: &str) -> String {
+    name.split('_')
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars.next()
+                .into_iter()
+                .flat_map(char::to_uppercase)
+                .chain(chars.flat_map(char::to_lowercase))
+        })
+        .collect()
 }
 
 impl FlagsInput {
@@ -25,9 +81,11 @@ impl Parse for FlagsInput {
         _=input.parse::<Token![use]>()?;
         let vexillo_crate = input.parse()?;
         _=input.parse::<Token![;]>()?;
-        let type_def = input.parse::<TypeDef>()?;
+        let mut type_def = input.parse::<TypeDef>()?;
+        let options = FlagsOptions::extract(&mut type_def.attrs)?;
         let config = input.parse::<OverrideBlock>()?;
-        let consts = input.parse::<ConstBlock>()?.build();
+        let lock = options.lock.as_ref().map(Lockfile::load).transpose()?;
+        let consts = input.parse::<ConstBlock>()?.build(lock.as_ref())?;
         if (consts.singles.len() + consts.groups.len()) > 65536 {
             return Err(
                 syn::Error::new(
@@ -36,11 +94,54 @@ impl Parse for FlagsInput {
                 )
             );
         }
+        if type_def.storage == Storage::Integer {
+            // Aliases can only be checked after expansion (see `FlagsInput::to_tokens`).
+            let mask_bits = ["u8", "u16", "u32", "u64", "u128"]
+                .into_iter()
+                .find(|name| matches!(&type_def.mask_type, syn::Type::Path(path) if path.path.is_ident(name)))
+                .map(|name| name[1..].parse::<u32>().unwrap());
+            if let Some(mask_bits) = mask_bits && consts.bit_count() > mask_bits {
+                return Err(syn::Error::new_spanned(
+                    &type_def.mask_type,
+                    format!(
+                        "The flags need {} bits, which do not fit in `{}`. Use a larger integer or `[{}]` to store the flags in multiple masks.",
+                        consts.bit_count(),
+                        type_def.mask_type.to_token_stream(),
+                        type_def.mask_type.to_token_stream(),
+                    ),
+                ));
+            }
+        }
+        let flag_enum = options.flag_enum
+            .as_ref()
+            .map(|flag_enum| {
+                let name = flag_enum.name
+                    .clone()
+                    .unwrap_or_else(|| format_ident!("{}Flag", type_def.type_name.unraw()));
+                FlagEnum::new(name, &consts.singles)
+            })
+            .transpose()?;
+        // Written last so that the lockfile only changes when the macro succeeds.
+        // Nothing is written unless `VEXILLO_UPDATE_LOCK` is set.
+        if let Some(lock) = &lock {
+            let flags = consts.singles.iter()
+                .map(|single| (single.ident.unraw().to_string(), single.index))
+                .collect::<Vec<_>>();
+            lock.save(&type_def.type_name.unraw().to_string(), &flags)?;
+        }
+        let c_header = options.c_header
+            .as_ref()
+            .map(|c_header| c_header::build(&type_def, &consts, c_header.name.as_ref()))
+            .transpose()?;
         Ok(Self {
             vexillo_crate,
             type_def,
+            options,
             config,
             consts,
+            flag_enum,
+            c_header,
+            lock_path: lock.map(|lock| lock.path),
         })
     }
 }
@@ -53,9 +154,11 @@ impl ToTokens for FlagsInput {
             type_name,
             mask_vis,
             mask_type,
+            storage,
         } = &self.type_def;
         let single_flag_count = self.consts.singles.len();
         let group_flag_count = self.consts.groups.len();
+        let index_span = self.consts.bit_count();
         let add_fn = syn::parse_quote!(add);
         let config = &self.config;
         let add_fn = config.get_alt(&add_fn).unwrap_or(&add_fn);
@@ -75,7 +178,72 @@ impl ToTokens for FlagsInput {
                     builder.#add_fn(#type_name::#ident);
                 )
             }).collect::<proc_macro2::TokenStream>();
-        let flag_consts = self.consts.tokenize(&config);
+        let flag_consts = self.consts.tokenize(config);
+        let (field, storage_consts, storage_check) = match storage {
+            Storage::Array => (
+                quote!(#mask_vis masks: [#mask_type; #type_name::MASK_COUNT]),
+                quote!(
+                    pub const MASK_COUNT: usize = {
+                        let mask_bits = #type_name::MASK_BITS as usize;
+                        let mask_bits_sub1 = mask_bits - 1;
+                        (#type_name::INDEX_SPAN as usize + mask_bits_sub1) / mask_bits
+                    };
+                    const LAST_MASK_INDEX: usize = #type_name::MASK_COUNT - 1;
+                    pub const NONE: #type_name = #type_name { masks: [0; #type_name::MASK_COUNT] };
+                ),
+                quote!(),
+            ),
+            Storage::Integer => {
+                let message = format!(
+                    "The flags of `{type_name}` do not fit in `{}`.",
+                    mask_type.to_token_stream(),
+                );
+                (
+                    quote!(#mask_vis bits: #mask_type),
+                    quote!(
+                        pub const MASK_COUNT: usize = 1;
+                        pub const NONE: #type_name = #type_name { bits: 0 };
+                    ),
+                    quote!(
+                        const _: () = assert!(#type_name::INDEX_SPAN <= #type_name::MASK_BITS, #message);
+                    ),
+                )
+            },
+        };
+        // Const access to single bits under names that the override block can't change,
+        // for macros such as `convert_by_name!` that only know the type.
+        let bit_access = {
+            let (get, set, clear) = match storage {
+                Storage::Array => (
+                    quote!(self.masks[(index / #type_name::MASK_BITS) as usize] & (1 << (index % #type_name::MASK_BITS)) != 0),
+                    quote!(self.masks[(index / #type_name::MASK_BITS) as usize] |= 1 << (index % #type_name::MASK_BITS)),
+                    quote!(self.masks[(index / #type_name::MASK_BITS) as usize] &= !(1 << (index % #type_name::MASK_BITS))),
+                ),
+                Storage::Integer => (
+                    quote!(self.bits & (1 << index) != 0),
+                    quote!(self.bits |= 1 << index),
+                    quote!(self.bits &= !(1 << index)),
+                ),
+            };
+            quote!(
+                #[doc(hidden)]
+                #[inline(always)]
+                #[must_use]
+                pub const fn __vexillo_get_bit(&self, index: u32) -> bool {
+                    #get
+                }
+                
+                #[doc(hidden)]
+                #[inline(always)]
+                pub const fn __vexillo_set_bit(&mut self, index: u32, on: bool) {
+                    if on {
+                        #set;
+                    } else {
+                        #clear;
+                    }
+                }
+            )
+        };
         let builtin_consts = quote!{
             // ################################
             // #          CONSTANTS           #
@@ -83,46 +251,104 @@ impl ToTokens for FlagsInput {
             pub const SINGLE_FLAG_COUNT: usize = #single_flag_count;
             pub const GROUP_FLAG_COUNT: usize = #group_flag_count;
             pub const TOTAL_FLAG_COUNT: usize = #type_name::SINGLE_FLAG_COUNT + #type_name::GROUP_FLAG_COUNT;
-            pub const BITS: u32 = (#type_name::SINGLE_FLAG_COUNT as u32).next_multiple_of(#type_name::MASK_BITS);
+            pub const INDEX_SPAN: u32 = #index_span;
+            pub const BITS: u32 = #type_name::INDEX_SPAN.next_multiple_of(#type_name::MASK_BITS);
             pub const UNUSED_BITS: u32 = (#type_name::BITS - #type_name::SINGLE_FLAG_COUNT as u32);
             pub const USED_BITS: u32 = (#type_name::BITS - #type_name::UNUSED_BITS);
             pub const MASK_BITS: u32 = #mask_type::BITS;
             pub const MASK_SIZE: usize = ::core::mem::size_of::<#mask_type>();
-            pub const MASK_COUNT: usize = {
-                let mask_bits = #type_name::MASK_BITS as usize;
-                let mask_bits_sub1 = mask_bits - 1;
-                (#type_name::SINGLE_FLAG_COUNT + mask_bits_sub1) / mask_bits
-            };
-            const LAST_MASK_INDEX: usize = #type_name::MASK_COUNT - 1;
-            pub const NONE: #type_name = #type_name { masks: [0; #type_name::MASK_COUNT] };
+            #storage_consts
             pub const ALL: #type_name = {
-                let mut builder = #type_name::new();
+                let mut builder = #type_name::NONE;
                 #all_builder
                 builder
             };
         };
+        let flag_tables = FlagTable::build(self).tokenize(&self.vexillo_crate);
         let functions_impl_block = build_builtin_functions(self);
         let op_impls = build_op_impls(self);
+        let flags_trait_impl = build_flags_trait_impl(self);
+        let fmt_impls = build_fmt_impls(self);
+        let serde_impls = build_serde_impls(self);
+        let arbitrary_impls = build_arbitrary_impls(self);
+        let exclusive_impls = build_exclusive_impls(self);
+        let implication_impls = build_implication_impls(self);
+        let atomic_impls = build_atomic_impls(self);
+        let flag_enum_impls = build_flag_enum_impls(self);
+        let derive_debug = match self.options.debug {
+            DebugStyle::Raw => quote!(Debug,),
+            DebugStyle::Names => quote!(),
+        };
         let vexillo = &self.vexillo_crate;
+        let warnings = &self.consts.warnings;
+        // The C header describes the struct, so its layout must be the C layout.
+        let (repr, c_header) = match &self.c_header {
+            Some(header) => (
+                quote!(C),
+                quote!(
+                    /// A C header with the struct, the masks of every flag, and helper functions.
+                    /// Build scripts can write it to a file.
+                    pub const C_HEADER: &'static str = #header;
+                ),
+            ),
+            None => (quote!(transparent), quote!()),
+        };
+        // Rebuild when the lockfile or the update variable changes.
+        // The lockfile isn't created until the update variable is set, so it may not exist.
+        let lock_tracking = self.lock_path.as_ref().map(|path| {
+            let include = path.exists().then(|| {
+                let path = path.to_string_lossy();
+                quote!(const _: &[u8] = ::core::include_bytes!(#path);)
+            });
+            let update_env = lock::UPDATE_ENV;
+            quote!(
+                #include
+                const _: ::core::option::Option<&str> = ::core::option_env!(#update_env);
+            )
+        });
         tokens.extend(quote!(
             #vexillo::mask_type_check!{#mask_type}
+            #lock_tracking
+            #(#warnings)*
             // ################################
             // #       TYPE DEFINITION        #
             // ################################
             #(#type_attrs)*
-            #[repr(transparent)]
-            #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #[repr(#repr)]
+            #[derive(#derive_debug Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
             #type_vis struct #type_name {
-                #mask_vis masks: [#mask_type; #type_name::MASK_COUNT],
+                #field,
             }
+            #storage_check
+            // The flag constants, tables, and `ALL` use flags that may be `#[deprecated]`.
+            #[allow(deprecated)]
             impl #type_name {
                 #builtin_consts
                 #flag_consts
+                #flag_tables
+                #c_header
+                #bit_access
             }
             
             #functions_impl_block
             
             #op_impls
+            
+            #flags_trait_impl
+            
+            #fmt_impls
+            
+            #serde_impls
+            
+            #arbitrary_impls
+            
+            #exclusive_impls
+            
+            #implication_impls
+            
+            #atomic_impls
+            
+            #flag_enum_impls
         ));
     }
 }
@@ -131,25 +357,175 @@ impl ToTokens for FlagsInput {
 // ###############################
 // #         BUILD TABLE         #
 // ###############################
-// fn build_flag_table(input: &FlagsInput) -> proc_macro2::TokenStream {
-//     let const_build = &input.consts;
-//     let mut all_flag_names = Vec::with_capacity(
-//         const_build.singles.len() + const_build.groups.len(),
-//     );
-//     all_flag_names.extend(
-//         const_build.singles.iter().map(|item| &item.ident)
-//     );
-//     let groups_start = all_flag_names.len();
-//     all_flag_names.extend(
-//         const_build.groups.iter().map(|item| &item.ident)
-//     );
-//     let singles = &all_flag_names[0..groups_start];
-//     let groups = &all_flag_names[groups_start..];
+/// The flag table, computed at macro time.
+/// 
+/// Rows are ordered first by [single, group], then singles by bit index
+/// and groups by declaration order.
+struct FlagTable<'a> {
+    idents: Vec<&'a Ident>,
+    /// Whether each row is marked `#[deprecated]`.
+    deprecated: Vec<bool>,
+    /// The name and row index of each alias, ordered by name.
+    aliases: Vec<(String, u16)>,
+    /// The row of the group that each row is declared in.
+    parents: Vec<Option<usize>>,
+    /// For each row, the rows that are declared directly in it, in declaration order.
+    children: Vec<Vec<usize>>,
+    /// The bit index of each single flag.
+    single_indices: Vec<u32>,
+    /// For each group, the row indices of the single flags that make up the group.
+    sub_flag_indices: Vec<Vec<u16>>,
+    single_count: usize,
+}
+
+impl<'a> FlagTable<'a> {
+    fn build(input: &'a FlagsInput) -> Self {
+        let consts = &input.consts;
+        let mut idents = Vec::with_capacity(consts.singles.len() + consts.groups.len());
+        idents.extend(consts.singles.iter().map(|single| &single.ident));
+        idents.extend(consts.groups.iter().map(|group| &group.ident));
+        let is_deprecated = |attrs: &[syn::Attribute]| attrs.iter().any(|attr| attr.path().is_ident("deprecated"));
+        let deprecated = consts.singles.iter()
+            .map(|single| is_deprecated(&single.attrs))
+            .chain(consts.groups.iter().map(|group| is_deprecated(&group.attrs)))
+            .collect();
+        let mut aliases = consts.aliases
+            .iter()
+            .map(|alias| {
+                let row = idents.iter()
+                    .position(|&ident| *ident == alias.target)
+                    .expect("Alias targets are verified by the const block.");
+                (alias.ident.unraw().to_string(), row as u16)
+            })
+            .collect::<Vec<_>>();
+        aliases.sort();
+        let rows = idents.iter()
+            .enumerate()
+            .map(|(row, &ident)| (ident, row))
+            .collect::<HashMap<_, _>>();
+        let parents = consts.singles.iter()
+            .map(|single| &single.parent)
+            .chain(consts.groups.iter().map(|group| &group.parent))
+            .map(|parent| parent.as_ref().map(|parent| rows[parent]))
+            .collect();
+        let children = consts.singles.iter()
+            .map(|_| Vec::new())
+            .chain(consts.groups.iter().map(|group| {
+                group.updates.iter()
+                    .filter_map(|update| match update {
+                        ConstGroupUpdate::Declare(ident) => Some(rows[ident]),
+                        _ => None,
+                    })
+                    .collect()
+            }))
+            .collect();
+        let bit_rows = consts.singles
+            .iter()
+            .enumerate()
+            .map(|(row, single)| (single.index, row as u16))
+            .collect::<HashMap<u32, u16>>();
+        let sub_flag_indices = consts.group_masks
+            .iter()
+            .map(|mask| {
+                mask.ones()
+                    .map(|bit| bit_rows[&bit])
+                    .collect::<Vec<_>>()
+            }).collect();
+        Self {
+            idents,
+            deprecated,
+            aliases,
+            parents,
+            children,
+            single_indices: consts.singles.iter().map(|single| single.index).collect(),
+            sub_flag_indices,
+            single_count: consts.singles.len(),
+        }
+    }
+    
+    fn name(&self, row: usize) -> String {
+        self.idents[row].unraw().to_string()
+    }
     
-//     let table_len = singles.len() + groups.len();
-//     let single_count = singles.len();
-//     let groups_count = groups.len();
-// }
+    fn bits(&self, row: usize) -> usize {
+        if row < self.single_count {
+            1
+        } else {
+            self.sub_flag_indices[row - self.single_count].len()
+        }
+    }
+    
+    fn name_ordered(&self, rows: std::ops::Range<usize>) -> Vec<u16> {
+        let mut ordered = rows.map(|row| (self.name(row), row as u16)).collect::<Vec<_>>();
+        ordered.sort();
+        ordered.into_iter().map(|(_, row)| row).collect()
+    }
+    
+    fn bit_ordered(&self, rows: std::ops::Range<usize>) -> Vec<u16> {
+        let mut ordered = rows.map(|row| row as u16).collect::<Vec<_>>();
+        // Stable sort keeps table order for rows with equal bit counts.
+        ordered.sort_by_key(|&row| std::cmp::Reverse(self.bits(row as usize)));
+        ordered
+    }
+    
+    fn rows(&self, vexillo: &Path) -> Vec<proc_macro2::TokenStream> {
+        self.idents
+            .iter()
+            .enumerate()
+            .map(|(row, ident)| {
+                let name = self.name(row);
+                let deprecated = self.deprecated[row].then(|| quote!(.with_deprecated()));
+                let ancestors = std::iter::successors(self.parents[row], |&parent| self.parents[parent])
+                    .map(|parent| self.idents[parent])
+                    .collect::<Vec<_>>();
+                let children = self.children[row].iter().map(|&child| self.idents[child]);
+                let hierarchy = (!ancestors.is_empty() || !self.children[row].is_empty()).then(|| {
+                    quote!(.with_hierarchy(&[#(Self::#ancestors),*], &[#(Self::#children),*]))
+                });
+                if row < self.single_count {
+                    let index = self.single_indices[row];
+                    quote!(#vexillo::FlagRow::single(#name, Self::#ident, #index)#deprecated #hierarchy)
+                } else {
+                    let sub_flags = &self.sub_flag_indices[row - self.single_count];
+                    quote!(
+                        #vexillo::FlagRow::group(
+                            #name,
+                            Self::#ident,
+                            &[#(#vexillo::FlagIndex::new(#sub_flags)),*],
+                        )#deprecated #hierarchy
+                    )
+                }
+            }).collect()
+    }
+    
+    /// Builds the `TABLES` constant.
+    fn tokenize(&self, vexillo: &Path) -> proc_macro2::TokenStream {
+        let rows = self.rows(vexillo);
+        let total_count = rows.len();
+        let single_count = self.single_count;
+        let group_count = total_count - single_count;
+        let name_ordered_rows = self.name_ordered(0..total_count);
+        let bit_ordered_rows = self.bit_ordered(0..total_count);
+        let name_ordered_singles = self.name_ordered(0..single_count);
+        let name_ordered_groups = self.name_ordered(single_count..total_count);
+        let bit_ordered_groups = self.bit_ordered(single_count..total_count);
+        let aliases = self.aliases.iter().map(|(name, row)| {
+            quote!(#vexillo::FlagAlias::new(#name, #vexillo::FlagIndex::new(#row)))
+        });
+        quote!(
+            /// Metadata for every single and group flag.
+            pub const TABLES: #vexillo::FlagTables<Self, #total_count, #single_count, #group_count> = #vexillo::FlagTables {
+                rows: [#(#rows,)*],
+                name_ordered_row_indices: [#(#vexillo::FlagIndex::new(#name_ordered_rows),)*],
+                bit_ordered_row_indices: [#(#vexillo::FlagIndex::new(#bit_ordered_rows),)*],
+                name_ordered_single_indices: [#(#vexillo::FlagIndex::new(#name_ordered_singles),)*],
+                name_ordered_group_indices: [#(#vexillo::FlagIndex::new(#name_ordered_groups),)*],
+                bit_ordered_group_indices: [#(#vexillo::FlagIndex::new(#bit_ordered_groups),)*],
+                aliases: &[#(#aliases,)*],
+            };
+        )
+    }
+}
 
 macro_rules! docstr {
     ($doc:literal $(, $($($name:ident = )? $arg:expr),*)?$(,)?) => {
@@ -206,6 +582,8 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
     
     let type_name = input.type_name();
     let vexillo = &input.vexillo_crate;
+    // Integer storage gets straight-line versions of the functions that work on the masks.
+    let integer = input.type_def.storage == Storage::Integer;
     // ################################
     // #          FUNCTIONS           #
     // ################################
@@ -254,240 +632,479 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
             builder
         }
     );
-    // func!( // try_find
-    //     #[doc("Try to find a flag by its name. Returns [None] if the flag was not found.")]
-    //     #[must_use]
-    //     const fn try_find(name: &str) -> Option<Self> {
-    //         use std::cmp::Ordering::*;
-    //         let mut lo = 0usize;
-    //         // ORDERED_FLAGS = [(name, value)]
-    //         let mut hi = Self::ORDERED_FLAGS.len();
-    //         while lo < hi {
-    //             let mid = ((hi - lo) / 2) + lo;
-    //             let mid_name = Self::ORDERED_FLAGS[mid].0;
-    //             match #vexillo::internal::const_cmp_str(name, mid_name) {
-    //                 Less => hi = mid,
-    //                 Equal => return Some(Self::ORDERED_FLAGS[mid].1),
-    //                 Greater => lo = mid + 1,
-    //             }
-    //         }
-    //         None
-    //     }
-    // );
-    // func!( // find
-    //     #[doc("Find a flag by its name. Panics if the flag was not found.")]
-    //     #[must_use]
-    //     #[track_caller]
-    //     #[inline]
-    //     const fn find(name: &str) -> Self {
-    //         match Self::try_find(name) {
-    //             Some(flag) => flag,
-    //             _ => panic!("Flag by that name was not found."),
-    //         }
-    //     }
-    // );
-    // func!( // find_or
-    //     #[doc("Find a flag by its name. Returns `default` if the flag was not found.")]
-    //     #[must_use]
-    //     #[inline]
-    //     const fn find_or(name: &str, default: Self) -> Self {
-    //         match Self::try_find(name) {
-    //             Some(flag) => flag,
-    //             _ => default,
-    //         }
-    //     }
-    // );
-    // func!( // find_or_none
-    //     #[doc("Find a flag by its name. Returns [Self::NONE] if the flag was not found.")]
-    //     #[must_use]
-    //     #[inline]
-    //     const fn find_or_none(name: &str) -> Self {
-    //         match Self::try_find(name) {
-    //             Some(flag) => flag,
-    //             _ => Self::NONE,
-    //         }
-    //     }
-    // );
-    func!( // count_ones
-        #[doc("Return the number of ones in the binary representation of `self`.")]
+    func!( // try_find
+        #[doc("Try to find a flag by its name or alias. Returns [None] if the flag was not found.")]
         #[must_use]
-        const fn count_ones(self) -> u32 {
-            let mut count = 0u32;
-            let mut index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = index.next() {
-                count += self.masks[i].count_ones();
+        const fn try_find(name: &str) -> Option<Self> {
+            match Self::TABLES.find(name) {
+                Some(row) => Some(row.value),
+                None => None,
             }
-            count
         }
     );
-    func!( // count_zeros
-        #[doc("Return the number of zeros in the binary representation of `self`.")]
+    func!( // find
+        #[doc("Find a flag by its name. Panics if the flag was not found.")]
         #[must_use]
-        const fn count_zeros(self) -> u32 {
-            let mut count = 0u32;
-            let mut index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = index.next() {
-                count += self.masks[i].count_zeros();
+        #[track_caller]
+        #[inline]
+        const fn find(name: &str) -> Self {
+            match Self::try_find(name) {
+                Some(flag) => flag,
+                _ => panic!("Flag by that name was not found."),
             }
-            // Unused bits are guaranteed to be set to 0.
-            count - Self::UNUSED_BITS
         }
     );
-    func!( // get
-        #[doc("Get the bit at `index`.")]
+    func!( // find_or
+        #[doc("Find a flag by its name. Returns `default` if the flag was not found.")]
         #[must_use]
-        #[track_caller]
-        const fn get(self, index: u32) -> bool {
-            assert!(index < Self::SINGLE_FLAG_COUNT as u32, "Index out of bounds.");
-            let index = #vexillo::internal::MaskIndex::new(index, Self::MASK_BITS);
-            self.masks[index.mask] & (1 << index.bit) != 0
+        #[inline]
+        const fn find_or(name: &str, default: Self) -> Self {
+            match Self::try_find(name) {
+                Some(flag) => flag,
+                _ => default,
+            }
         }
     );
-    func!( // set
-        #[doc("Set the bit at `index`.")]
-        #[track_caller]
-        const fn set(&mut self, index: u32, on: bool) -> &mut Self {
-            assert!(index < Self::SINGLE_FLAG_COUNT as u32, "Index out of bounds.");
-            let index = #vexillo::internal::MaskIndex::new(index, Self::MASK_BITS);
-            if on {
-                self.masks[index.mask] |= (1 << index.bit);
-            } else {
-                self.masks[index.mask] &= !(1 << index.bit);
+    func!( // find_or_none
+        #[doc("Find a flag by its name. Returns [{type_name}::NONE] if the flag was not found.")]
+        #[must_use]
+        #[inline]
+        const fn find_or_none(name: &str) -> Self {
+            match Self::try_find(name) {
+                Some(flag) => flag,
+                _ => Self::NONE,
             }
-            self
         }
     );
-    func!( // swap
-        #[doc("Swap the bit at `index`.")]
-        const fn swap(&mut self, index: u32, on: bool) -> bool {
-            assert!(index < Self::SINGLE_FLAG_COUNT as u32, "Index out of bounds.");
-            let index = #vexillo::internal::MaskIndex::new(index, Self::MASK_BITS);
-            let old = ((self.masks[index.mask] & (1 << index.bit)) != 0);
-            if on {
-                self.masks[index.mask] |= (1 << index.bit);
-            } else {
-                self.masks[index.mask] &= !(1 << index.bit);
-            }
-            old
+    func!( // row_of
+        #[doc("Find the row of the flag table for `flag`. Returns [None] if `flag` is not a single or group flag.\n\nFlags are found by value, so single flags are found before groups, and groups with the same flags are found in declaration order. Use [{type_name}::TABLES] to find a row by name.")]
+        #[must_use]
+        const fn row_of(flag: Self) -> Option<&'static #vexillo::FlagRow<Self>> {
+            let rows = &Self::TABLES.rows;
+            let mut i = 0;
+            while i < rows.len() {
+                if Self::eq(rows[i].value, flag) {
+                    return Some(&rows[i]);
+                }
+                i += 1;
+            }
+            None
         }
     );
-    func!( // from_index
-        #[doc("Create a [{type_name}] with the bit at the given `index` set to 1.")]
+    func!( // parent_of
+        #[doc("Return the group that `flag` is declared in. Returns [None] for flags at the top level and for unknown flags.\n\nFlags with the same value are found as described in [{type_name}::row_of]. Use [{type_name}::parent_of_by_name] to tell them apart.")]
+        #[must_use]
         #[inline]
+        const fn parent_of(flag: Self) -> Option<Self> {
+            match Self::row_of(flag) {
+                Some(row) => row.parent(),
+                None => None,
+            }
+        }
+    );
+    func!( // parent_of_by_name
+        #[doc("Return the group that the flag named `name` is declared in. Returns [None] for flags at the top level and for unknown names.")]
         #[must_use]
-        #[track_caller]
-        const fn from_index(index: u32) -> Self {
-            let mut builder = Self::NONE;
-            builder.set(index, true);
-            builder
+        #[inline]
+        const fn parent_of_by_name(name: &str) -> Option<Self> {
+            match Self::TABLES.find(name) {
+                Some(row) => row.parent(),
+                None => None,
+            }
         }
     );
-    func!( // leading_zeros
-        #[doc("Count the number of leading zeros.")]
+    func!( // children_of
+        #[doc("Return the flags and groups that are declared directly in `group`, in declaration order.\n\nUnlike [{type_name}::row_of], only groups are searched, so a group with the same value as a single flag still has children. Use [{type_name}::children_of_by_name] to tell groups with the same value apart.")]
         #[must_use]
-        const fn leading_zeros(self) -> u32 {
-            //   leading | trailing
-            // 0b000000011100000000
-            // UNUSED_BITS bitmask example (UNUSED_BITS is bit-count, not bitmask)
-            // 0b11110000
-            let valid_mask = self.masks[Self::LAST_MASK_INDEX] & Self::ALL.masks[Self::LAST_MASK_INDEX];
-            let lead = valid_mask.leading_zeros();
-            if lead < Self::MASK_BITS {
-                // valid_mask was mask ANDed with ALL, removing the unused bits.
-                return lead - Self::UNUSED_BITS;
-            }
-            let mut count = lead - Self::UNUSED_BITS;
-            let mut index = Self::LAST_MASK_INDEX;
-            while index != 0 {
-                index -= 1;
-                let lead = self.masks[index].leading_zeros();
-                count += lead;
-                if lead < Self::MASK_BITS {
-                    return count;
-                }
-            }
-            Self::USED_BITS
+        const fn children_of(group: Self) -> &'static [Self] {
+            let groups = Self::TABLES.groups().rows;
+            let mut i = 0;
+            while i < groups.len() {
+                if Self::eq(groups[i].value, group) {
+                    return groups[i].children();
+                }
+                i += 1;
+            }
+            &[]
         }
     );
-    func!( // leading_ones
-        #[doc("Count the number of leading ones.")]
+    func!( // children_of_by_name
+        #[doc("Return the flags and groups that are declared directly in the group named `name`, in declaration order.")]
         #[must_use]
-        const fn leading_ones(self) -> u32 {
-            //   leading | trailing
-            // 0b000000011100000000
-            let with_unused = self.masks[Self::LAST_MASK_INDEX] | !Self::ALL.masks[Self::LAST_MASK_INDEX];
-            let lead = with_unused.leading_ones();
-            if lead < Self::MASK_BITS {
-                return lead - Self::UNUSED_BITS;
+        #[inline]
+        const fn children_of_by_name(name: &str) -> &'static [Self] {
+            match Self::TABLES.find(name) {
+                Some(row) => row.children(),
+                None => &[],
             }
-            let mut count = lead - Self::UNUSED_BITS;
-            let mut index = Self::LAST_MASK_INDEX;
-            while index != 0 {
-                index -= 1;
-                let lead = self.masks[index].leading_ones();
-                count += lead;
-                if lead < Self::MASK_BITS {
-                    return count;
-                }
+        }
+    );
+    func!( // ancestors_of
+        #[doc("Return the groups that `flag` is declared in, from its parent to the top level.\n\nFlags with the same value are found as described in [{type_name}::row_of]. Use [{type_name}::ancestors_of_by_name] to tell them apart.")]
+        #[must_use]
+        #[inline]
+        const fn ancestors_of(flag: Self) -> &'static [Self] {
+            match Self::row_of(flag) {
+                Some(row) => row.ancestors(),
+                None => &[],
             }
-            
-            Self::USED_BITS
         }
     );
-    func!( // trailing_zeros
-        #[doc("Count the number of trailing zeros.")]
+    func!( // ancestors_of_by_name
+        #[doc("Return the groups that the flag named `name` is declared in, from its parent to the top level.")]
         #[must_use]
-        const fn trailing_zeros(self) -> u32 {
-            //   leading | trailing
-            // 0b000000011100000000
-            let mut index = #vexillo::internal::ConstCounter::new(0usize);
-            let mut count = 0u32;
-            while let i @ 0..Self::MASK_COUNT = index.next() {
-                let trailing = self.masks[i].trailing_zeros();
-                count += trailing;
-                if trailing < Self::MASK_BITS {
-                    return if count > Self::USED_BITS {
-                        Self::USED_BITS
-                    } else {
-                        count
-                    };
-                }
+        #[inline]
+        const fn ancestors_of_by_name(name: &str) -> &'static [Self] {
+            match Self::TABLES.find(name) {
+                Some(row) => row.ancestors(),
+                None => &[],
             }
-            Self::USED_BITS
         }
     );
-    func!( // trailing_ones
-        #[doc("Count the number of trailing ones.")]
+    func!( // depth_of
+        #[doc("Return the number of groups that `flag` is declared in. Returns [None] for unknown flags.\n\nFlags with the same value are found as described in [{type_name}::row_of]. Use [{type_name}::depth_of_by_name] to tell them apart.")]
         #[must_use]
-        const fn trailing_ones(self) -> u32 {
-            //   leading | trailing
-            // 0b000000011100000000
-            let mut index = #vexillo::internal::ConstCounter::new(0usize);
-            let mut count = 0u32;
-            while let i @ 0..Self::MASK_COUNT = index.next() {
-                let trailing = self.masks[i].trailing_ones();
-                count += trailing;
-                if trailing < Self::MASK_BITS {
-                    return if count > Self::USED_BITS {
-                        Self::USED_BITS
-                    } else {
-                        count
-                    };
-                }
+        #[inline]
+        const fn depth_of(flag: Self) -> Option<usize> {
+            match Self::row_of(flag) {
+                Some(row) => Some(row.depth()),
+                None => None,
+            }
+        }
+    );
+    func!( // depth_of_by_name
+        #[doc("Return the number of groups that the flag named `name` is declared in. Returns [None] for unknown names.")]
+        #[must_use]
+        #[inline]
+        const fn depth_of_by_name(name: &str) -> Option<usize> {
+            match Self::TABLES.find(name) {
+                Some(row) => Some(row.depth()),
+                None => None,
             }
-            Self::USED_BITS
         }
     );
-    func!( // add
-        #[doc("Add all of the bits present in `flag`.")]
-        const fn add(&mut self, flag: Self) -> &mut Self {
-            let mut index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = index.next() {
-                self.masks[i] |= flag.masks[i];
+    if integer {
+        func!( // count_ones
+            #[doc("Return the number of ones in the binary representation of `self`.")]
+            #[inline]
+            #[must_use]
+            const fn count_ones(self) -> u32 {
+                self.bits.count_ones()
             }
-            self
+        );
+    } else {
+        func!( // count_ones
+            #[doc("Return the number of ones in the binary representation of `self`.")]
+            #[must_use]
+            const fn count_ones(self) -> u32 {
+                let mut count = 0u32;
+                let mut index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = index.next() {
+                    count += self.masks[i].count_ones();
+                }
+                count
+            }
+        );
+    }
+    if integer {
+        func!( // count_zeros
+            #[doc("Return the number of zeros in the binary representation of `self`.")]
+            #[inline]
+            #[must_use]
+            const fn count_zeros(self) -> u32 {
+                // Unused bits are guaranteed to be set to 0.
+                self.bits.count_zeros() - Self::UNUSED_BITS
+            }
+        );
+    } else {
+        func!( // count_zeros
+            #[doc("Return the number of zeros in the binary representation of `self`.")]
+            #[must_use]
+            const fn count_zeros(self) -> u32 {
+                let mut count = 0u32;
+                let mut index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = index.next() {
+                    count += self.masks[i].count_zeros();
+                }
+                // Unused bits are guaranteed to be set to 0.
+                count - Self::UNUSED_BITS
+            }
+        );
+    }
+    if integer {
+        func!( // get
+            #[doc("Get the bit at `index`.")]
+            #[inline]
+            #[must_use]
+            #[track_caller]
+            const fn get(self, index: u32) -> bool {
+                assert!(index < Self::INDEX_SPAN, "Index out of bounds.");
+                self.bits & (1 << index) != 0
+            }
+        );
+    } else {
+        func!( // get
+            #[doc("Get the bit at `index`.")]
+            #[must_use]
+            #[track_caller]
+            const fn get(self, index: u32) -> bool {
+                assert!(index < Self::INDEX_SPAN, "Index out of bounds.");
+                let index = #vexillo::internal::MaskIndex::new(index, Self::MASK_BITS);
+                self.masks[index.mask] & (1 << index.bit) != 0
+            }
+        );
+    }
+    if integer {
+        func!( // set
+            #[doc("Set the bit at `index`.")]
+            #[inline]
+            #[track_caller]
+            const fn set(&mut self, index: u32, on: bool) -> &mut Self {
+                assert!(index < Self::INDEX_SPAN, "Index out of bounds.");
+                if on {
+                    self.bits |= 1 << index;
+                } else {
+                    self.bits &= !(1 << index);
+                }
+                self
+            }
+        );
+    } else {
+        func!( // set
+            #[doc("Set the bit at `index`.")]
+            #[track_caller]
+            const fn set(&mut self, index: u32, on: bool) -> &mut Self {
+                assert!(index < Self::INDEX_SPAN, "Index out of bounds.");
+                let index = #vexillo::internal::MaskIndex::new(index, Self::MASK_BITS);
+                if on {
+                    self.masks[index.mask] |= (1 << index.bit);
+                } else {
+                    self.masks[index.mask] &= !(1 << index.bit);
+                }
+                self
+            }
+        );
+    }
+    if integer {
+        func!( // swap
+            #[doc("Swap the bit at `index`.")]
+            #[inline]
+            const fn swap(&mut self, index: u32, on: bool) -> bool {
+                assert!(index < Self::INDEX_SPAN, "Index out of bounds.");
+                let old = self.bits & (1 << index) != 0;
+                if on {
+                    self.bits |= 1 << index;
+                } else {
+                    self.bits &= !(1 << index);
+                }
+                old
+            }
+        );
+    } else {
+        func!( // swap
+            #[doc("Swap the bit at `index`.")]
+            const fn swap(&mut self, index: u32, on: bool) -> bool {
+                assert!(index < Self::INDEX_SPAN, "Index out of bounds.");
+                let index = #vexillo::internal::MaskIndex::new(index, Self::MASK_BITS);
+                let old = ((self.masks[index.mask] & (1 << index.bit)) != 0);
+                if on {
+                    self.masks[index.mask] |= (1 << index.bit);
+                } else {
+                    self.masks[index.mask] &= !(1 << index.bit);
+                }
+                old
+            }
+        );
+    }
+    func!( // from_index
+        #[doc("Create a [{type_name}] with the bit at the given `index` set to 1.")]
+        #[inline]
+        #[must_use]
+        #[track_caller]
+        const fn from_index(index: u32) -> Self {
+            let mut builder = Self::NONE;
+            builder.set(index, true);
+            builder
         }
     );
+    if integer {
+        func!( // leading_zeros
+            #[doc("Count the number of leading zeros below `INDEX_SPAN`.")]
+            #[inline]
+            #[must_use]
+            const fn leading_zeros(self) -> u32 {
+                // The bits at or above INDEX_SPAN are padding, so they are shifted out.
+                let lead = (self.bits << (Self::MASK_BITS - Self::INDEX_SPAN)).leading_zeros();
+                if lead < Self::INDEX_SPAN {
+                    lead
+                } else {
+                    Self::INDEX_SPAN
+                }
+            }
+        );
+    } else {
+        func!( // leading_zeros
+            #[doc("Count the number of leading zeros below `INDEX_SPAN`.")]
+            #[must_use]
+            const fn leading_zeros(self) -> u32 {
+                //   leading | trailing
+                // 0b000000011100000000
+                // The bits at or above INDEX_SPAN are padding, so they are shifted out of the last mask.
+                let padding = Self::BITS - Self::INDEX_SPAN;
+                let span_bits = Self::MASK_BITS - padding;
+                let lead = (self.masks[Self::LAST_MASK_INDEX] << padding).leading_zeros();
+                if lead < span_bits {
+                    return lead;
+                }
+                let mut count = span_bits;
+                let mut index = Self::LAST_MASK_INDEX;
+                while index != 0 {
+                    index -= 1;
+                    let lead = self.masks[index].leading_zeros();
+                    count += lead;
+                    if lead < Self::MASK_BITS {
+                        return count;
+                    }
+                }
+                Self::INDEX_SPAN
+            }
+        );
+    }
+    if integer {
+        func!( // leading_ones
+            #[doc("Count the number of leading ones below `INDEX_SPAN`.")]
+            #[inline]
+            #[must_use]
+            const fn leading_ones(self) -> u32 {
+                // Shifting the padding out fills the low bits with zeros, which stops the count.
+                (self.bits << (Self::MASK_BITS - Self::INDEX_SPAN)).leading_ones()
+            }
+        );
+    } else {
+        func!( // leading_ones
+            #[doc("Count the number of leading ones below `INDEX_SPAN`.")]
+            #[must_use]
+            const fn leading_ones(self) -> u32 {
+                //   leading | trailing
+                // 0b000000011100000000
+                // Shifting the padding out fills the low bits with zeros, which stops the count.
+                let padding = Self::BITS - Self::INDEX_SPAN;
+                let span_bits = Self::MASK_BITS - padding;
+                let lead = (self.masks[Self::LAST_MASK_INDEX] << padding).leading_ones();
+                if lead < span_bits {
+                    return lead;
+                }
+                let mut count = span_bits;
+                let mut index = Self::LAST_MASK_INDEX;
+                while index != 0 {
+                    index -= 1;
+                    let lead = self.masks[index].leading_ones();
+                    count += lead;
+                    if lead < Self::MASK_BITS {
+                        return count;
+                    }
+                }
+                Self::INDEX_SPAN
+            }
+        );
+    }
+    if integer {
+        func!( // trailing_zeros
+            #[doc("Count the number of trailing zeros below `INDEX_SPAN`.")]
+            #[inline]
+            #[must_use]
+            const fn trailing_zeros(self) -> u32 {
+                let trailing = self.bits.trailing_zeros();
+                if trailing < Self::INDEX_SPAN {
+                    trailing
+                } else {
+                    Self::INDEX_SPAN
+                }
+            }
+        );
+    } else {
+        func!( // trailing_zeros
+            #[doc("Count the number of trailing zeros below `INDEX_SPAN`.")]
+            #[must_use]
+            const fn trailing_zeros(self) -> u32 {
+                //   leading | trailing
+                // 0b000000011100000000
+                let mut index = #vexillo::internal::ConstCounter::new(0usize);
+                let mut count = 0u32;
+                while let i @ 0..Self::MASK_COUNT = index.next() {
+                    let trailing = self.masks[i].trailing_zeros();
+                    count += trailing;
+                    if trailing < Self::MASK_BITS {
+                        return if count > Self::INDEX_SPAN {
+                            Self::INDEX_SPAN
+                        } else {
+                            count
+                        };
+                    }
+                }
+                Self::INDEX_SPAN
+            }
+        );
+    }
+    if integer {
+        func!( // trailing_ones
+            #[doc("Count the number of trailing ones below `INDEX_SPAN`.")]
+            #[inline]
+            #[must_use]
+            const fn trailing_ones(self) -> u32 {
+                let trailing = self.bits.trailing_ones();
+                if trailing < Self::INDEX_SPAN {
+                    trailing
+                } else {
+                    Self::INDEX_SPAN
+                }
+            }
+        );
+    } else {
+        func!( // trailing_ones
+            #[doc("Count the number of trailing ones below `INDEX_SPAN`.")]
+            #[must_use]
+            const fn trailing_ones(self) -> u32 {
+                //   leading | trailing
+                // 0b000000011100000000
+                let mut index = #vexillo::internal::ConstCounter::new(0usize);
+                let mut count = 0u32;
+                while let i @ 0..Self::MASK_COUNT = index.next() {
+                    let trailing = self.masks[i].trailing_ones();
+                    count += trailing;
+                    if trailing < Self::MASK_BITS {
+                        return if count > Self::INDEX_SPAN {
+                            Self::INDEX_SPAN
+                        } else {
+                            count
+                        };
+                    }
+                }
+                Self::INDEX_SPAN
+            }
+        );
+    }
+    if integer {
+        func!( // add
+            #[doc("Add all of the bits present in `flag`.")]
+            #[inline]
+            const fn add(&mut self, flag: Self) -> &mut Self {
+                self.bits |= flag.bits;
+                self
+            }
+        );
+    } else {
+        func!( // add
+            #[doc("Add all of the bits present in `flag`.")]
+            const fn add(&mut self, flag: Self) -> &mut Self {
+                let mut index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = index.next() {
+                    self.masks[i] |= flag.masks[i];
+                }
+                self
+            }
+        );
+    }
     func!( // add_if
         #[doc("Add all of the bits present in `flag` if `condition` is `true`.")]
         #[inline]
@@ -519,16 +1136,27 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
             self
         }
     );
-    func!( // remove
-        #[doc("Remove all of the bits present in `flag`.")]
-        const fn remove(&mut self, flag: Self) -> &mut Self {
-            let mut index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = index.next() {
-                self.masks[i] &= !flag.masks[i];
+    if integer {
+        func!( // remove
+            #[doc("Remove all of the bits present in `flag`.")]
+            #[inline]
+            const fn remove(&mut self, flag: Self) -> &mut Self {
+                self.bits &= !flag.bits;
+                self
             }
-            self
-        }
-    );
+        );
+    } else {
+        func!( // remove
+            #[doc("Remove all of the bits present in `flag`.")]
+            const fn remove(&mut self, flag: Self) -> &mut Self {
+                let mut index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = index.next() {
+                    self.masks[i] &= !flag.masks[i];
+                }
+                self
+            }
+        );
+    }
     func!( // remove_if
         #[doc("Remove all of the bits present in `flag` if `condition` is `true`.")]
         #[inline]
@@ -632,226 +1260,447 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
             self
         }
     );
-    func!( // has_all
-        #[doc("Test if all bits of `flag` are present in `self`.")]
-        #[must_use]
-        const fn has_all(self, flag: Self) -> bool {
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                if self.masks[i] & flag.masks[i] != flag.masks[i] {
-                    return false;
+    if integer {
+        func!( // has_all
+            #[doc("Test if all bits of `flag` are present in `self`.")]
+            #[inline]
+            #[must_use]
+            const fn has_all(self, flag: Self) -> bool {
+                self.bits & flag.bits == flag.bits
+            }
+        );
+    } else {
+        func!( // has_all
+            #[doc("Test if all bits of `flag` are present in `self`.")]
+            #[must_use]
+            const fn has_all(self, flag: Self) -> bool {
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    if self.masks[i] & flag.masks[i] != flag.masks[i] {
+                        return false;
+                    }
                 }
+                true
             }
-            true
-        }
-    );
-    func!( // has_none
-        #[doc("Test if none of the bits of `flag` are present in `self`.")]
-        #[must_use]
-        const fn has_none(self, flag: Self) -> bool {
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                if self.masks[i] & flag.masks[i] != 0 {
-                    return false;
+        );
+    }
+    if integer {
+        func!( // has_none
+            #[doc("Test if none of the bits of `flag` are present in `self`.")]
+            #[inline]
+            #[must_use]
+            const fn has_none(self, flag: Self) -> bool {
+                self.bits & flag.bits == 0
+            }
+        );
+    } else {
+        func!( // has_none
+            #[doc("Test if none of the bits of `flag` are present in `self`.")]
+            #[must_use]
+            const fn has_none(self, flag: Self) -> bool {
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    if self.masks[i] & flag.masks[i] != 0 {
+                        return false;
+                    }
                 }
+                true
             }
-            true
-        }
-    );
-    func!( // has_any
-        #[doc("Test if any of the bits of `flag` are present in `self`.")]
-        #[must_use]
-        const fn has_any(self, flag: Self) -> bool {
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                if self.masks[i] & flag.masks[i] != 0 {
-                    return true;
+        );
+    }
+    if integer {
+        func!( // has_any
+            #[doc("Test if any of the bits of `flag` are present in `self`.")]
+            #[inline]
+            #[must_use]
+            const fn has_any(self, flag: Self) -> bool {
+                self.bits & flag.bits != 0
+            }
+        );
+    } else {
+        func!( // has_any
+            #[doc("Test if any of the bits of `flag` are present in `self`.")]
+            #[must_use]
+            const fn has_any(self, flag: Self) -> bool {
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    if self.masks[i] & flag.masks[i] != 0 {
+                        return true;
+                    }
                 }
+                false
             }
-            false
-        }
-    );
-    func!( // has_some
-        #[doc("Test if some but not all of the bits of `flag` are present in `self`.")]
-        #[must_use]
-        const fn has_some(self, flag: Self) -> bool {
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            let mut any_ne = false;
-            let mut has_any = false;
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                any_ne |= self.masks[i] != flag.masks[i];
-                has_any |= self.masks[i] & flag.masks[i] != 0;
+        );
+    }
+    if integer {
+        func!( // has_some
+            #[doc("Test if some but not all of the bits of `flag` are present in `self`.")]
+            #[inline]
+            #[must_use]
+            const fn has_some(self, flag: Self) -> bool {
+                self.bits & flag.bits != 0 && self.bits != flag.bits
             }
-            has_any && any_ne
-        }
-    );
+        );
+    } else {
+        func!( // has_some
+            #[doc("Test if some but not all of the bits of `flag` are present in `self`.")]
+            #[must_use]
+            const fn has_some(self, flag: Self) -> bool {
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                let mut any_ne = false;
+                let mut has_any = false;
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    any_ne |= self.masks[i] != flag.masks[i];
+                    has_any |= self.masks[i] & flag.masks[i] != 0;
+                }
+                has_any && any_ne
+            }
+        );
+    }
     let mask_ty = &input.type_def.mask_type;
-    func!( // as_slice
-        #[doc("Returns the inner `masks` as a slice.")]
-        #[inline]
-        #[must_use]
-        const fn as_slice(&self) -> &[#mask_ty] {
-            &self.masks
-        }
-    );
-    func!( // as_mut_slice
-        #[doc("Returns the inner `masks` as a mutable slice.")]
-        #[inline]
-        #[must_use]
-        const fn as_mut_slice(&mut self) -> &mut [#mask_ty] {
-            &mut self.masks
-        }
-    );
-    func!( // into_inner
-        #[doc("Decompose `self` into the inner `masks` array.")]
-        #[inline]
-        #[must_use]
-        const fn into_inner(self) -> [#mask_ty; Self::MASK_COUNT] {
-            self.masks
-        }
-    );
-    func!( // as_bytes
-        #[doc("Return `self` as a slice of bytes. Endianess is dependent on the target architecture. This is meant to be used for FFI. If you need serialization, use [{type_name}::to_be_bytes], [{type_name}::to_le_bytes], or [{type_name}::to_ne_bytes].")]
-        #[inline]
-        #[must_use]
-        const fn as_bytes(&self) -> &[u8] {
-            unsafe {
-                ::core::slice::from_raw_parts(
-                    self.masks.as_ptr().cast::<u8>(),
-                    ::core::mem::size_of::<Self>(),
-                )
+    if integer {
+        func!( // as_slice
+            #[doc("Returns the inner `bits` as a slice.")]
+            #[inline]
+            #[must_use]
+            const fn as_slice(&self) -> &[#mask_ty] {
+                ::core::slice::from_ref(&self.bits)
             }
-        }
-    );
-    
-    func!( // as_mut_bytes
-        #[doc("Return `self` as a mutable slice of bytes. Endianess is dependent on the target architecture. This is meant to be used for FFI. If you need serialization, use [{type_name}::to_be_bytes], [{type_name}::to_le_bytes], or [{type_name}::to_ne_bytes].")]
-        #[inline]
-        #[must_use]
-        const fn as_mut_bytes(&mut self) -> &mut [u8] {
-            unsafe {
-                ::core::slice::from_raw_parts_mut(
-                    self.masks.as_mut_ptr().cast::<u8>(),
-                    ::core::mem::size_of::<Self>(),
-                )
+        );
+    } else {
+        func!( // as_slice
+            #[doc("Returns the inner `masks` as a slice.")]
+            #[inline]
+            #[must_use]
+            const fn as_slice(&self) -> &[#mask_ty] {
+                &self.masks
             }
-        }
-    );
-    func!( // to_be_bytes
-        #[doc("Return `self` as Big-Endian bytes.")]
-        #[must_use]
-        const fn to_be_bytes(self) -> [u8; ::core::mem::size_of::<Self>()] {
-            let mut bytes = [0u8; ::core::mem::size_of::<Self>()];
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                let offset = i * Self::MASK_SIZE;
-                let mask_bytes = self.masks[i].to_be_bytes();
-                unsafe {
-                    let dst_ptr = bytes.as_mut_ptr().add(offset);
-                    ::core::ptr::copy_nonoverlapping(mask_bytes.as_ptr(), dst_ptr, Self::MASK_SIZE);
+        );
+    }
+    if integer {
+        func!( // as_mut_slice
+            #[doc("Returns the inner `bits` as a mutable slice.")]
+            #[inline]
+            #[must_use]
+            const fn as_mut_slice(&mut self) -> &mut [#mask_ty] {
+                ::core::slice::from_mut(&mut self.bits)
+            }
+        );
+    } else {
+        func!( // as_mut_slice
+            #[doc("Returns the inner `masks` as a mutable slice.")]
+            #[inline]
+            #[must_use]
+            const fn as_mut_slice(&mut self) -> &mut [#mask_ty] {
+                &mut self.masks
+            }
+        );
+    }
+    if integer {
+        func!( // into_inner
+            #[doc("Decompose `self` into an array holding the inner `bits`.")]
+            #[inline]
+            #[must_use]
+            const fn into_inner(self) -> [#mask_ty; Self::MASK_COUNT] {
+                [self.bits]
+            }
+        );
+    } else {
+        func!( // into_inner
+            #[doc("Decompose `self` into the inner `masks` array.")]
+            #[inline]
+            #[must_use]
+            const fn into_inner(self) -> [#mask_ty; Self::MASK_COUNT] {
+                self.masks
+            }
+        );
+    }
+    if integer {
+        func!( // bits
+            #[doc("Return the inner `bits`.")]
+            #[inline(always)]
+            #[must_use]
+            const fn bits(self) -> #mask_ty {
+                self.bits
+            }
+        );
+        func!( // from_bits
+            #[doc("Create a [{type_name}] from `bits`. Returns [None] if any bit is not part of a flag.")]
+            #[inline]
+            #[must_use]
+            const fn from_bits(bits: #mask_ty) -> Option<Self> {
+                if bits & !Self::ALL.bits == 0 {
+                    Some(Self { bits })
+                } else {
+                    None
                 }
             }
-            bytes
-        }
-    );
-    func!( // from_be_bytes
-        #[doc("Create a new [{type_name}] from Big-Endian `bytes`.")]
-        #[must_use]
-        const fn from_be_bytes(bytes: [u8; ::core::mem::size_of::<Self>()]) -> Self {
-            let mut masks = [0; Self::MASK_COUNT];
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                let offset = i * Self::MASK_SIZE;
-                let sub = #vexillo::internal::subslice(&bytes, offset..offset+Self::MASK_SIZE);
-                let mut mask_bytes = [0u8; Self::MASK_SIZE];
-                mask_bytes.copy_from_slice(&sub);
-                masks[i] = #mask_ty::from_be_bytes(mask_bytes);
+        );
+        func!( // from_bits_truncate
+            #[doc("Create a [{type_name}] from `bits`, dropping the bits that are not part of a flag.")]
+            #[inline]
+            #[must_use]
+            const fn from_bits_truncate(bits: #mask_ty) -> Self {
+                Self {
+                    bits: bits & Self::ALL.bits,
+                }
             }
-            Self {
-                masks,
+        );
+        func!( // from_bits_retain
+            #[doc("Create a [{type_name}] from `bits`, keeping the bits that are not part of a flag.\n\nThe result might not be valid (see [{type_name}::is_valid]).")]
+            #[inline(always)]
+            #[must_use]
+            const fn from_bits_retain(bits: #mask_ty) -> Self {
+                Self { bits }
             }
-        }
-    );
-    func!( // to_le_bytes
-        #[doc("Return `self` as Little-Endian bytes.")]
-        #[must_use]
-        const fn to_le_bytes(self) -> [u8; ::core::mem::size_of::<Self>()] {
-            let mut bytes = [0u8; ::core::mem::size_of::<Self>()];
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                let offset = i * Self::MASK_SIZE;
-                let mask_bytes = self.masks[i].to_le_bytes();
+        );
+    }
+    if integer {
+        func!( // as_bytes
+            #[doc("Return `self` as a slice of bytes. Endianess is dependent on the target architecture. This is meant to be used for FFI. If you need serialization, use [{type_name}::to_be_bytes], [{type_name}::to_le_bytes], or [{type_name}::to_ne_bytes].")]
+            #[inline]
+            #[must_use]
+            const fn as_bytes(&self) -> &[u8] {
                 unsafe {
-                    let dst_ptr = bytes.as_mut_ptr().add(offset);
-                    ::core::ptr::copy_nonoverlapping(mask_bytes.as_ptr(), dst_ptr, Self::MASK_SIZE);
+                    ::core::slice::from_raw_parts(
+                        (&self.bits as *const #mask_ty).cast::<u8>(),
+                        ::core::mem::size_of::<Self>(),
+                    )
                 }
             }
-            bytes
-        }
-    );
-    func!( // from_le_bytes
-        #[doc("Create a new [{type_name}] from Little-Endian `bytes`.")]
-        #[must_use]
-        const fn from_le_bytes(bytes: [u8; ::core::mem::size_of::<Self>()]) -> Self {
-            let mut masks = [0; Self::MASK_COUNT];
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                let offset = i * Self::MASK_SIZE;
-                let sub = #vexillo::internal::subslice(&bytes, offset..offset+Self::MASK_SIZE);
-                let mut mask_bytes = [0u8; Self::MASK_SIZE];
-                mask_bytes.copy_from_slice(&sub);
-                masks[i] = #mask_ty::from_le_bytes(mask_bytes);
+        );
+    } else {
+        func!( // as_bytes
+            #[doc("Return `self` as a slice of bytes. Endianess is dependent on the target architecture. This is meant to be used for FFI. If you need serialization, use [{type_name}::to_be_bytes], [{type_name}::to_le_bytes], or [{type_name}::to_ne_bytes].")]
+            #[inline]
+            #[must_use]
+            const fn as_bytes(&self) -> &[u8] {
+                unsafe {
+                    ::core::slice::from_raw_parts(
+                        self.masks.as_ptr().cast::<u8>(),
+                        ::core::mem::size_of::<Self>(),
+                    )
+                }
             }
-            Self {
-                masks,
+        );
+    }
+    
+    if integer {
+        func!( // as_mut_bytes
+            #[doc("Return `self` as a mutable slice of bytes. Endianess is dependent on the target architecture. This is meant to be used for FFI. If you need serialization, use [{type_name}::to_be_bytes], [{type_name}::to_le_bytes], or [{type_name}::to_ne_bytes].")]
+            #[inline]
+            #[must_use]
+            const fn as_mut_bytes(&mut self) -> &mut [u8] {
+                unsafe {
+                    ::core::slice::from_raw_parts_mut(
+                        (&mut self.bits as *mut #mask_ty).cast::<u8>(),
+                        ::core::mem::size_of::<Self>(),
+                    )
+                }
             }
-        }
-    );
-    func!( // to_ne_bytes
-        #[doc("Return `self` as Native-Endian bytes.")]
-        #[must_use]
-        const fn to_ne_bytes(self) -> [u8; ::core::mem::size_of::<Self>()] {
-            let mut bytes = [0u8; ::core::mem::size_of::<Self>()];
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                let offset = i * Self::MASK_SIZE;
-                let mask_bytes = self.masks[i].to_ne_bytes();
+        );
+    } else {
+        func!( // as_mut_bytes
+            #[doc("Return `self` as a mutable slice of bytes. Endianess is dependent on the target architecture. This is meant to be used for FFI. If you need serialization, use [{type_name}::to_be_bytes], [{type_name}::to_le_bytes], or [{type_name}::to_ne_bytes].")]
+            #[inline]
+            #[must_use]
+            const fn as_mut_bytes(&mut self) -> &mut [u8] {
                 unsafe {
-                    let dst_ptr = bytes.as_mut_ptr().add(offset);
-                    ::core::ptr::copy_nonoverlapping(mask_bytes.as_ptr(), dst_ptr, Self::MASK_SIZE);
+                    ::core::slice::from_raw_parts_mut(
+                        self.masks.as_mut_ptr().cast::<u8>(),
+                        ::core::mem::size_of::<Self>(),
+                    )
                 }
             }
-            bytes
-        }
-    );
-    func!( // from_ne_bytes
-        #[doc("Create a new [{type_name}] from Native-Endian `bytes`.")]
-        #[must_use]
-        const fn from_ne_bytes(bytes: [u8; ::core::mem::size_of::<Self>()]) -> Self {
-            let mut masks = [0; Self::MASK_COUNT];
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                let offset = i * Self::MASK_SIZE;
-                let sub = #vexillo::internal::subslice(&bytes, offset..offset+Self::MASK_SIZE);
-                let mut mask_bytes = [0u8; Self::MASK_SIZE];
-                mask_bytes.copy_from_slice(&sub);
-                masks[i] = #mask_ty::from_ne_bytes(mask_bytes);
+        );
+    }
+    if integer {
+        func!( // to_be_bytes
+            #[doc("Return `self` as Big-Endian bytes.")]
+            #[inline]
+            #[must_use]
+            const fn to_be_bytes(self) -> [u8; ::core::mem::size_of::<Self>()] {
+                self.bits.to_be_bytes()
             }
-            Self {
-                masks,
+        );
+    } else {
+        func!( // to_be_bytes
+            #[doc("Return `self` as Big-Endian bytes.")]
+            #[must_use]
+            const fn to_be_bytes(self) -> [u8; ::core::mem::size_of::<Self>()] {
+                let mut bytes = [0u8; ::core::mem::size_of::<Self>()];
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    let offset = i * Self::MASK_SIZE;
+                    let mask_bytes = self.masks[i].to_be_bytes();
+                    unsafe {
+                        let dst_ptr = bytes.as_mut_ptr().add(offset);
+                        ::core::ptr::copy_nonoverlapping(mask_bytes.as_ptr(), dst_ptr, Self::MASK_SIZE);
+                    }
+                }
+                bytes
             }
-        }
-    );
+        );
+    }
+    if integer {
+        func!( // from_be_bytes
+            #[doc("Create a new [{type_name}] from Big-Endian `bytes`.")]
+            #[inline]
+            #[must_use]
+            const fn from_be_bytes(bytes: [u8; ::core::mem::size_of::<Self>()]) -> Self {
+                Self {
+                    bits: #mask_ty::from_be_bytes(bytes),
+                }
+            }
+        );
+    } else {
+        func!( // from_be_bytes
+            #[doc("Create a new [{type_name}] from Big-Endian `bytes`.")]
+            #[must_use]
+            const fn from_be_bytes(bytes: [u8; ::core::mem::size_of::<Self>()]) -> Self {
+                let mut masks = [0; Self::MASK_COUNT];
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    let offset = i * Self::MASK_SIZE;
+                    let sub = #vexillo::internal::subslice(&bytes, offset..offset+Self::MASK_SIZE);
+                    let mut mask_bytes = [0u8; Self::MASK_SIZE];
+                    mask_bytes.copy_from_slice(&sub);
+                    masks[i] = #mask_ty::from_be_bytes(mask_bytes);
+                }
+                Self {
+                    masks,
+                }
+            }
+        );
+    }
+    if integer {
+        func!( // to_le_bytes
+            #[doc("Return `self` as Little-Endian bytes.")]
+            #[inline]
+            #[must_use]
+            const fn to_le_bytes(self) -> [u8; ::core::mem::size_of::<Self>()] {
+                self.bits.to_le_bytes()
+            }
+        );
+    } else {
+        func!( // to_le_bytes
+            #[doc("Return `self` as Little-Endian bytes.")]
+            #[must_use]
+            const fn to_le_bytes(self) -> [u8; ::core::mem::size_of::<Self>()] {
+                let mut bytes = [0u8; ::core::mem::size_of::<Self>()];
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    let offset = i * Self::MASK_SIZE;
+                    let mask_bytes = self.masks[i].to_le_bytes();
+                    unsafe {
+                        let dst_ptr = bytes.as_mut_ptr().add(offset);
+                        ::core::ptr::copy_nonoverlapping(mask_bytes.as_ptr(), dst_ptr, Self::MASK_SIZE);
+                    }
+                }
+                bytes
+            }
+        );
+    }
+    if integer {
+        func!( // from_le_bytes
+            #[doc("Create a new [{type_name}] from Little-Endian `bytes`.")]
+            #[inline]
+            #[must_use]
+            const fn from_le_bytes(bytes: [u8; ::core::mem::size_of::<Self>()]) -> Self {
+                Self {
+                    bits: #mask_ty::from_le_bytes(bytes),
+                }
+            }
+        );
+    } else {
+        func!( // from_le_bytes
+            #[doc("Create a new [{type_name}] from Little-Endian `bytes`.")]
+            #[must_use]
+            const fn from_le_bytes(bytes: [u8; ::core::mem::size_of::<Self>()]) -> Self {
+                let mut masks = [0; Self::MASK_COUNT];
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    let offset = i * Self::MASK_SIZE;
+                    let sub = #vexillo::internal::subslice(&bytes, offset..offset+Self::MASK_SIZE);
+                    let mut mask_bytes = [0u8; Self::MASK_SIZE];
+                    mask_bytes.copy_from_slice(&sub);
+                    masks[i] = #mask_ty::from_le_bytes(mask_bytes);
+                }
+                Self {
+                    masks,
+                }
+            }
+        );
+    }
+    if integer {
+        func!( // to_ne_bytes
+            #[doc("Return `self` as Native-Endian bytes.")]
+            #[inline]
+            #[must_use]
+            const fn to_ne_bytes(self) -> [u8; ::core::mem::size_of::<Self>()] {
+                self.bits.to_ne_bytes()
+            }
+        );
+    } else {
+        func!( // to_ne_bytes
+            #[doc("Return `self` as Native-Endian bytes.")]
+            #[must_use]
+            const fn to_ne_bytes(self) -> [u8; ::core::mem::size_of::<Self>()] {
+                let mut bytes = [0u8; ::core::mem::size_of::<Self>()];
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    let offset = i * Self::MASK_SIZE;
+                    let mask_bytes = self.masks[i].to_ne_bytes();
+                    unsafe {
+                        let dst_ptr = bytes.as_mut_ptr().add(offset);
+                        ::core::ptr::copy_nonoverlapping(mask_bytes.as_ptr(), dst_ptr, Self::MASK_SIZE);
+                    }
+                }
+                bytes
+            }
+        );
+    }
+    if integer {
+        func!( // from_ne_bytes
+            #[doc("Create a new [{type_name}] from Native-Endian `bytes`.")]
+            #[inline]
+            #[must_use]
+            const fn from_ne_bytes(bytes: [u8; ::core::mem::size_of::<Self>()]) -> Self {
+                Self {
+                    bits: #mask_ty::from_ne_bytes(bytes),
+                }
+            }
+        );
+    } else {
+        func!( // from_ne_bytes
+            #[doc("Create a new [{type_name}] from Native-Endian `bytes`.")]
+            #[must_use]
+            const fn from_ne_bytes(bytes: [u8; ::core::mem::size_of::<Self>()]) -> Self {
+                let mut masks = [0; Self::MASK_COUNT];
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    let offset = i * Self::MASK_SIZE;
+                    let sub = #vexillo::internal::subslice(&bytes, offset..offset+Self::MASK_SIZE);
+                    let mut mask_bytes = [0u8; Self::MASK_SIZE];
+                    mask_bytes.copy_from_slice(&sub);
+                    masks[i] = #mask_ty::from_ne_bytes(mask_bytes);
+                }
+                Self {
+                    masks,
+                }
+            }
+        );
+    }
     func!( // decompose
-        #[doc("Decompose bits into booleans.")]
+        #[doc("Decompose bits into booleans, indexed by bit index.")]
         #[must_use]
-        const fn decompose(self) -> [bool; Self::SINGLE_FLAG_COUNT] {
-            // let mut bools = [false; Self::SINGLE_FLAG_COUNT];
-            let mut bools: [::core::mem::MaybeUninit<bool>; Self::SINGLE_FLAG_COUNT] = unsafe {
+        const fn decompose(self) -> [bool; Self::INDEX_SPAN as usize] {
+            // let mut bools = [false; Self::INDEX_SPAN as usize];
+            let mut bools: [::core::mem::MaybeUninit<bool>; Self::INDEX_SPAN as usize] = unsafe {
                 ::core::mem::MaybeUninit::uninit().assume_init()
             };
             let mut index = 0usize;
-            while index < Self::SINGLE_FLAG_COUNT {
+            while index < Self::INDEX_SPAN as usize {
                 bools[index].write(self.get(index as u32));
                 index += 1;
             }
@@ -862,30 +1711,41 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
         }
     );
     func!( // compose
-        #[doc("Compose booleans into bits.")]
+        #[doc("Compose booleans, indexed by bit index, into bits.")]
         #[must_use]
-        const fn compose(decomposed: &[bool; Self::SINGLE_FLAG_COUNT]) -> Self {
+        const fn compose(decomposed: &[bool; Self::INDEX_SPAN as usize]) -> Self {
             let mut new = Self::NONE;
             let mut index = 0usize;
-            while index < Self::SINGLE_FLAG_COUNT {
+            while index < Self::INDEX_SPAN as usize {
                 new.set(index as u32, decomposed[index]);
                 index += 1;
             }
             new
         }
     );
-    func!( // not_assign
-        #[doc("Bitwise NOT with assignment.")]
-        const fn not_assign(&mut self) -> &mut Self {
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                self.masks[i] = !self.masks[i];
-            }
-            // Ensure that the unused bits are not set.
-            self.masks[Self::LAST_MASK_INDEX] &= Self::ALL.masks[Self::LAST_MASK_INDEX];
-            self
-        }
-    );
+    if integer {
+        func!( // not_assign
+            #[doc("Bitwise NOT with assignment.")]
+            #[inline]
+            const fn not_assign(&mut self) -> &mut Self {
+                // Ensure that the unused bits are not set.
+                self.bits = !self.bits & Self::ALL.bits;
+                self
+            }
+        );
+    } else {
+        func!( // not_assign
+            #[doc("Bitwise NOT with assignment.")]
+            const fn not_assign(&mut self) -> &mut Self {
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    // Ensure that the unused bits are not set.
+                    self.masks[i] = !self.masks[i] & Self::ALL.masks[i];
+                }
+                self
+            }
+        );
+    }
     func!( // not
         #[doc("Bitwise NOT.")]
         #[inline]
@@ -895,16 +1755,27 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
             self
         }
     );
-    func!( // and_assign
-        #[doc("Bitwise AND with assignment.")]
-        const fn and_assign(&mut self, other: Self) -> &mut Self {
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                self.masks[i] &= other.masks[i];
+    if integer {
+        func!( // and_assign
+            #[doc("Bitwise AND with assignment.")]
+            #[inline]
+            const fn and_assign(&mut self, other: Self) -> &mut Self {
+                self.bits &= other.bits;
+                self
             }
-            self
-        }
-    );
+        );
+    } else {
+        func!( // and_assign
+            #[doc("Bitwise AND with assignment.")]
+            const fn and_assign(&mut self, other: Self) -> &mut Self {
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    self.masks[i] &= other.masks[i];
+                }
+                self
+            }
+        );
+    }
     func!( // and
         #[doc("Bitwise AND.")]
         #[inline]
@@ -914,16 +1785,27 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
             self
         }
     );
-    func!( // or_assign
-        #[doc("Bitwise OR with assignment.")]
-        const fn or_assign(&mut self, other: Self) -> &mut Self {
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                self.masks[i] |= other.masks[i];
+    if integer {
+        func!( // or_assign
+            #[doc("Bitwise OR with assignment.")]
+            #[inline]
+            const fn or_assign(&mut self, other: Self) -> &mut Self {
+                self.bits |= other.bits;
+                self
             }
-            self
-        }
-    );
+        );
+    } else {
+        func!( // or_assign
+            #[doc("Bitwise OR with assignment.")]
+            const fn or_assign(&mut self, other: Self) -> &mut Self {
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    self.masks[i] |= other.masks[i];
+                }
+                self
+            }
+        );
+    }
     func!( // or
         #[doc("Bitwise OR.")]
         #[inline]
@@ -933,16 +1815,27 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
             self
         }
     );
-    func!( // xor_assign
-        #[doc("Bitwise XOR with assignment")]
-        const fn xor_assign(&mut self, other: Self) -> &mut Self {
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                self.masks[i] ^= other.masks[i];
+    if integer {
+        func!( // xor_assign
+            #[doc("Bitwise XOR with assignment")]
+            #[inline]
+            const fn xor_assign(&mut self, other: Self) -> &mut Self {
+                self.bits ^= other.bits;
+                self
             }
-            self
-        }
-    );
+        );
+    } else {
+        func!( // xor_assign
+            #[doc("Bitwise XOR with assignment")]
+            const fn xor_assign(&mut self, other: Self) -> &mut Self {
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    self.masks[i] ^= other.masks[i];
+                }
+                self
+            }
+        );
+    }
     func!( // xor
         #[doc("Bitwise XOR.")]
         #[inline]
@@ -952,17 +1845,27 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
             self
         }
     );
-    func!( // nand_assign
-        #[doc("Bitwise NAND with assignment.")]
-        const fn nand_assign(&mut self, other: Self) -> &mut Self {
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                self.masks[i] = !(self.masks[i] & other.masks[i]);
-            }
-            self.masks[Self::LAST_MASK_INDEX] &= Self::ALL.masks[Self::LAST_MASK_INDEX];
-            self
-        }
-    );
+    if integer {
+        func!( // nand_assign
+            #[doc("Bitwise NAND with assignment.")]
+            #[inline]
+            const fn nand_assign(&mut self, other: Self) -> &mut Self {
+                self.bits = !(self.bits & other.bits) & Self::ALL.bits;
+                self
+            }
+        );
+    } else {
+        func!( // nand_assign
+            #[doc("Bitwise NAND with assignment.")]
+            const fn nand_assign(&mut self, other: Self) -> &mut Self {
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    self.masks[i] = !(self.masks[i] & other.masks[i]) & Self::ALL.masks[i];
+                }
+                self
+            }
+        );
+    }
     func!( // nand
         #[doc("Bitwise NAND.")]
         #[inline]
@@ -972,17 +1875,27 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
             self
         }
     );
-    func!( // nor_assign
-        #[doc("Bitwise NOR with assignment.")]
-        const fn nor_assign(&mut self, other: Self) -> &mut Self {
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                self.masks[i] = !self.masks[i] & !other.masks[i];
-            }
-            self.masks[Self::LAST_MASK_INDEX] &= Self::ALL.masks[Self::LAST_MASK_INDEX];
-            self
-        }
-    );
+    if integer {
+        func!( // nor_assign
+            #[doc("Bitwise NOR with assignment.")]
+            #[inline]
+            const fn nor_assign(&mut self, other: Self) -> &mut Self {
+                self.bits = !self.bits & !other.bits & Self::ALL.bits;
+                self
+            }
+        );
+    } else {
+        func!( // nor_assign
+            #[doc("Bitwise NOR with assignment.")]
+            const fn nor_assign(&mut self, other: Self) -> &mut Self {
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    self.masks[i] = !self.masks[i] & !other.masks[i] & Self::ALL.masks[i];
+                }
+                self
+            }
+        );
+    }
     func!( // nor
         #[doc("Bitwise NOR.")]
         #[inline]
@@ -992,16 +1905,27 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
             self
         }
     );
-    func!( // xnor_assign
-        #[doc("Bitwise XNOR with assignment.")]
-        const fn xnor_assign(&mut self, other: Self) -> &mut Self {
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                self.masks[i] = !(self.masks[i] ^ other.masks[i]);
+    if integer {
+        func!( // xnor_assign
+            #[doc("Bitwise XNOR with assignment.")]
+            #[inline]
+            const fn xnor_assign(&mut self, other: Self) -> &mut Self {
+                self.bits = !(self.bits ^ other.bits) & Self::ALL.bits;
+                self
             }
-            self
-        }
-    );
+        );
+    } else {
+        func!( // xnor_assign
+            #[doc("Bitwise XNOR with assignment.")]
+            const fn xnor_assign(&mut self, other: Self) -> &mut Self {
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    self.masks[i] = !(self.masks[i] ^ other.masks[i]) & Self::ALL.masks[i];
+                }
+                self
+            }
+        );
+    }
     func!( // xnor
         #[doc("Bitwise XNOR.")]
         #[inline]
@@ -1011,19 +1935,29 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
             self
         }
     );
-    func!( // imply_assign
-        #[doc("Bitwise IMPLY with assignment.")]
-        const fn imply_assign(&mut self, other: Self) -> &mut Self {
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                let a = self.masks[i];
-                let b = other.masks[i];
-                self.masks[i] = !a | b;
-            }
-            self.masks[Self::LAST_MASK_INDEX] &= Self::ALL.masks[Self::LAST_MASK_INDEX];
-            self
-        }
-    );
+    if integer {
+        func!( // imply_assign
+            #[doc("Bitwise IMPLY with assignment.")]
+            #[inline]
+            const fn imply_assign(&mut self, other: Self) -> &mut Self {
+                self.bits = (!self.bits | other.bits) & Self::ALL.bits;
+                self
+            }
+        );
+    } else {
+        func!( // imply_assign
+            #[doc("Bitwise IMPLY with assignment.")]
+            const fn imply_assign(&mut self, other: Self) -> &mut Self {
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    let a = self.masks[i];
+                    let b = other.masks[i];
+                    self.masks[i] = (!a | b) & Self::ALL.masks[i];
+                }
+                self
+            }
+        );
+    }
     func!( // imply
         #[doc("Bitwise IMPLY.")]
         #[inline]
@@ -1033,18 +1967,29 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
             self
         }
     );
-    func!( // nimply_assign
-        #[doc("Bitwise NIMPLY with assignment.")]
-        const fn nimply_assign(&mut self, other: Self) -> &mut Self {
-            let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = mask_index.next() {
-                let a = self.masks[i];
-                let b = other.masks[i];
-                self.masks[i] = a & !b;
+    if integer {
+        func!( // nimply_assign
+            #[doc("Bitwise NIMPLY with assignment.")]
+            #[inline]
+            const fn nimply_assign(&mut self, other: Self) -> &mut Self {
+                self.bits &= !other.bits;
+                self
             }
-            self
-        }
-    );
+        );
+    } else {
+        func!( // nimply_assign
+            #[doc("Bitwise NIMPLY with assignment.")]
+            const fn nimply_assign(&mut self, other: Self) -> &mut Self {
+                let mut mask_index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = mask_index.next() {
+                    let a = self.masks[i];
+                    let b = other.masks[i];
+                    self.masks[i] = a & !b;
+                }
+                self
+            }
+        );
+    }
     func!( // nimply
         #[doc("Bitwise NIMPLY.")]
         #[inline]
@@ -1054,32 +1999,54 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
             self
         }
     );
-    func!( // eq
-        #[doc("Test for equality.")]
-        #[must_use]
-        const fn eq(self, other: Self) -> bool {
-            let mut counter = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = counter.next() {
-                if self.masks[i] != other.masks[i] {
-                    return false;
+    if integer {
+        func!( // eq
+            #[doc("Test for equality.")]
+            #[inline]
+            #[must_use]
+            const fn eq(self, other: Self) -> bool {
+                self.bits == other.bits
+            }
+        );
+    } else {
+        func!( // eq
+            #[doc("Test for equality.")]
+            #[must_use]
+            const fn eq(self, other: Self) -> bool {
+                let mut counter = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = counter.next() {
+                    if self.masks[i] != other.masks[i] {
+                        return false;
+                    }
                 }
+                true
             }
-            true
-        }
-    );
-    func!( // ne
-        #[doc("Test for inequality.")]
-        #[must_use]
-        const fn ne(self, other: Self) -> bool {
-            let mut counter = #vexillo::internal::ConstCounter::new(0usize);
-            while let i @ 0..Self::MASK_COUNT = counter.next() {
-                if self.masks[i] != other.masks[i] {
-                    return true;
+        );
+    }
+    if integer {
+        func!( // ne
+            #[doc("Test for inequality.")]
+            #[inline]
+            #[must_use]
+            const fn ne(self, other: Self) -> bool {
+                self.bits != other.bits
+            }
+        );
+    } else {
+        func!( // ne
+            #[doc("Test for inequality.")]
+            #[must_use]
+            const fn ne(self, other: Self) -> bool {
+                let mut counter = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = counter.next() {
+                    if self.masks[i] != other.masks[i] {
+                        return true;
+                    }
                 }
+                false
             }
-            false
-        }
-    );
+        );
+    }
     func!( // is_empty
         #[doc("Test if there are no flags present.")]
         #[inline]
@@ -1104,14 +2071,168 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
             self.count_ones() as _
         }
     );
-    func!( // is_valid
-        #[doc("Test if this is a valid bitset. A valid bitset has none of the unused bits set.")]
+    if integer {
+        func!( // is_valid
+            #[doc("Test if this is a valid bitset. A valid bitset has none of the unused bits set.")]
+            #[inline]
+            #[must_use]
+            const fn is_valid(self) -> bool {
+                self.bits & !Self::ALL.bits == 0
+            }
+        );
+    } else {
+        func!( // is_valid
+            #[doc("Test if this is a valid bitset. A valid bitset has none of the unused bits set.")]
+            #[inline]
+            #[must_use]
+            const fn is_valid(self) -> bool {
+                let mut index = #vexillo::internal::ConstCounter::new(0usize);
+                while let i @ 0..Self::MASK_COUNT = index.next() {
+                    if self.masks[i] & !Self::ALL.masks[i] != 0 {
+                        return false;
+                    }
+                }
+                true
+            }
+        );
+    }
+    func!( // iter
+        #[doc("Iterate over the single flags that are set in `self`.")]
         #[inline]
         #[must_use]
-        const fn is_valid(self) -> bool {
-            self.masks[Self::LAST_MASK_INDEX] & !Self::ALL.masks[Self::LAST_MASK_INDEX] == 0
+        fn iter(self) -> #vexillo::Iter<Self> {
+            #vexillo::Iter::new(self)
         }
     );
+    func!( // iter_indices
+        #[doc("Iterate over the bit indices that are set in `self`.")]
+        #[inline]
+        #[must_use]
+        fn iter_indices(self) -> #vexillo::IterIndices<Self> {
+            #vexillo::IterIndices::new(self)
+        }
+    );
+    func!( // iter_names
+        #[doc("Iterate over the names of the single flags that are set in `self`.")]
+        #[inline]
+        #[must_use]
+        fn iter_names(self) -> #vexillo::IterNames<Self> {
+            #vexillo::IterNames::new(self)
+        }
+    );
+    func!( // iter_groups
+        #[doc("Iterate over the group flags that are fully contained in `self`. Empty groups are skipped.")]
+        #[inline]
+        #[must_use]
+        fn iter_groups(self) -> #vexillo::IterGroups<Self> {
+            #vexillo::IterGroups::new(self)
+        }
+    );
+    func!( // decompose_named
+        #[doc("Decompose `self` into the fewest named flags: the smallest set of groups that `self` fully contains, plus the single flags that they leave out.\n\nUnused bits are ignored.")]
+        #[inline]
+        #[must_use]
+        fn decompose_named(self) -> #vexillo::DecomposeNamed<Self> {
+            #vexillo::DecomposeNamed::new(self)
+        }
+    );
+    // Only generated when the const block declares exclusive sets.
+    if !input.consts.exclusive.is_empty() {
+        let checks = input.consts.exclusive.iter().enumerate().map(|(i, set)| {
+            let indices = &set.indices;
+            quote!({
+                let mut found = Self::NONE;
+                let mut count = 0u32;
+                #(
+                    if self.__vexillo_get_bit(#indices) {
+                        found.__vexillo_set_bit(#indices, true);
+                        count += 1;
+                    }
+                )*
+                if count > 1 {
+                    return ::core::result::Result::Err(#vexillo::Conflict::new(found, Self::EXCLUSIVE_SETS[#i]));
+                }
+            })
+        });
+        let clears = input.consts.exclusive.iter().map(|set| {
+            let indices = &set.indices;
+            quote!(
+                if #(flag.__vexillo_get_bit(#indices))||* {
+                    #(
+                        if !flag.__vexillo_get_bit(#indices) {
+                            self.__vexillo_set_bit(#indices, false);
+                        }
+                    )*
+                }
+            )
+        });
+        func!( // validate
+            #[doc("Check that at most one flag of each exclusive set is set. Returns the first set with more than one flag.")]
+            #[inline]
+            const fn validate(self) -> ::core::result::Result<(), #vexillo::Conflict<Self>> {
+                #(#checks)*
+                ::core::result::Result::Ok(())
+            }
+        );
+        func!( // set_exclusive
+            #[doc("Add `flag`, and remove the other flags of every exclusive set that `flag` is in.\n\nIf `flag` has more than one flag of an exclusive set, both are kept.")]
+            #[inline]
+            const fn set_exclusive(&mut self, flag: Self) -> &mut Self {
+                #(#clears)*
+                Self::or_assign(self, flag);
+                self
+            }
+        );
+    }
+    // Only generated when the const block declares implications.
+    if !input.consts.implications.is_empty() {
+        let applies = input.consts.implications.iter().map(|implication| {
+            let index = implication.index;
+            let implied = implication.implied.ones();
+            quote!(
+                if self.__vexillo_get_bit(#index) {
+                    #(normalized.__vexillo_set_bit(#implied, true);)*
+                }
+            )
+        });
+        let checks = input.consts.implications.iter().map(|implication| {
+            let index = implication.index;
+            let implied = implication.implied.ones();
+            quote!(
+                if self.__vexillo_get_bit(#index) && !(#(self.__vexillo_get_bit(#implied))&&*) {
+                    return false;
+                }
+            )
+        });
+        func!( // normalize
+            #[doc("Add the flags that are implied by the flags of `self`.")]
+            #[inline]
+            #[must_use]
+            const fn normalize(self) -> Self {
+                let mut normalized = self;
+                #(#applies)*
+                normalized
+            }
+        );
+        func!( // is_normalized
+            #[doc("Test if `self` has every flag that is implied by its flags.")]
+            #[inline]
+            #[must_use]
+            const fn is_normalized(self) -> bool {
+                #(#checks)*
+                true
+            }
+        );
+        func!( // with_implied
+            #[doc("Join with `flag` and every flag that it implies.")]
+            #[inline]
+            #[must_use]
+            const fn with_implied(mut self, flag: Self) -> Self {
+                Self::or_assign(&mut self, Self::normalize(flag));
+                self
+            }
+        );
+    }
     let inner = functions.into_iter().collect::<proc_macro2::TokenStream>();
     let type_name = input.type_name();
     let mut impl_block: syn::File = syn::parse_quote!(
@@ -1129,6 +2250,7 @@ fn build_builtin_functions(input: &FlagsInput) -> syn::File {
 
 fn build_op_impls(input: &FlagsInput) -> syn::File {
     let ty = input.type_name();
+    let vexillo = &input.vexillo_crate;
     /*
     Not,
     BitAnd, BitAndAssign,
@@ -1138,6 +2260,8 @@ fn build_op_impls(input: &FlagsInput) -> syn::File {
     Sub, SubAssign,
     Index<u32, Output = bool>
     Index<usize, Output = bool>
+    IntoIterator<Item = Self>
+    FromStr
     */
     let mut op_impls: syn::File = syn::parse_quote!(
         impl ::core::ops::Not for #ty {
@@ -1227,7 +2351,7 @@ fn build_op_impls(input: &FlagsInput) -> syn::File {
             type Output = bool;
             #[inline(always)]
             fn index(&self, index: u32) -> &bool {
-                debug_assert!((index as usize) < Self::SINGLE_FLAG_COUNT, "Index out of bounds.");
+                debug_assert!(index < Self::INDEX_SPAN, "Index out of bounds.");
                 const BOOLS: [bool; 2] = [false, true];
                 &BOOLS[self.get(index) as usize]
             }
@@ -1237,11 +2361,28 @@ fn build_op_impls(input: &FlagsInput) -> syn::File {
             type Output = bool;
             #[inline(always)]
             fn index(&self, index: usize) -> &bool {
-                debug_assert!(index < Self::SINGLE_FLAG_COUNT, "Index out of bounds.");
+                debug_assert!(index < Self::INDEX_SPAN as usize, "Index out of bounds.");
                 const BOOLS: [bool; 2] = [false, true];
                 &BOOLS[self.get(index as u32) as usize]
             }
         }
+        
+        impl ::core::iter::IntoIterator for #ty {
+            type Item = Self;
+            type IntoIter = #vexillo::Iter<Self>;
+            #[inline(always)]
+            fn into_iter(self) -> #vexillo::Iter<Self> {
+                self.iter()
+            }
+        }
+        
+        impl ::core::str::FromStr for #ty {
+            type Err = #vexillo::ParseFlagsError;
+            #[inline]
+            fn from_str(s: &str) -> ::core::result::Result<Self, #vexillo::ParseFlagsError> {
+                #vexillo::parse_flags(s)
+            }
+        }
     );
     let mut overrider = Overrider {
         overrides: &input.config,
@@ -1249,4 +2390,788 @@ fn build_op_impls(input: &FlagsInput) -> syn::File {
     };
     overrider.visit_file_mut(&mut op_impls);
     op_impls
-}
\ No newline at end of file
+}
+
+fn build_fmt_impls(input: &FlagsInput) -> proc_macro2::TokenStream {
+    let ty = input.type_name();
+    let vexillo = &input.vexillo_crate;
+    let debug_impl = match input.options.debug {
+        DebugStyle::Names => {
+            let name = ty.unraw().to_string();
+            quote!(
+                impl ::core::fmt::Debug for #ty {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                        #vexillo::fmt::debug(*self, #name, f)
+                    }
+                }
+            )
+        }
+        DebugStyle::Raw => quote!(),
+    };
+    let style = match input.options.display {
+        DisplayStyle::Names => quote!(#vexillo::fmt::NameStyle::Singles),
+        DisplayStyle::Groups => quote!(#vexillo::fmt::NameStyle::Groups),
+        DisplayStyle::None => return debug_impl,
+    };
+    quote!(
+        #debug_impl
+        
+        impl ::core::fmt::Display for #ty {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #vexillo::fmt::display(*self, #style, f)
+            }
+        }
+    )
+}
+
+fn build_serde_impls(input: &FlagsInput) -> proc_macro2::TokenStream {
+    if !cfg!(feature = "serde") {
+        return proc_macro2::TokenStream::new();
+    }
+    let ty = input.type_name();
+    let vexillo = &input.vexillo_crate;
+    let repr = match input.options.serde.repr {
+        SerdeRepr::Auto => quote!(Auto),
+        SerdeRepr::Names => quote!(Names),
+        SerdeRepr::Bytes => quote!(Bytes),
+        SerdeRepr::Masks => quote!(Masks),
+    };
+    let unknown = match input.options.serde.unknown {
+        SerdeUnknown::Error => quote!(Error),
+        SerdeUnknown::Ignore => quote!(Ignore),
+        SerdeUnknown::Preserve => quote!(Preserve),
+    };
+    quote!(
+        impl #vexillo::internal::serde::Serialize for #ty {
+            fn serialize<S: #vexillo::internal::serde::Serializer>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error> {
+                #vexillo::serde::serialize(*self, #vexillo::serde::Repr::#repr, serializer)
+            }
+        }
+        
+        impl<'de> #vexillo::internal::serde::Deserialize<'de> for #ty {
+            fn deserialize<D: #vexillo::internal::serde::Deserializer<'de>>(deserializer: D) -> ::core::result::Result<Self, D::Error> {
+                #vexillo::serde::deserialize(
+                    deserializer,
+                    #vexillo::serde::Repr::#repr,
+                    #vexillo::serde::Unknown::#unknown,
+                )
+            }
+        }
+    )
+}
+
+/// The `proptest` and `arbitrary` impls, for the features that are enabled.
+fn build_arbitrary_impls(input: &FlagsInput) -> proc_macro2::TokenStream {
+    let ty = input.type_name();
+    let vexillo = &input.vexillo_crate;
+    let mut tokens = proc_macro2::TokenStream::new();
+    if cfg!(feature = "proptest") {
+        tokens.extend(quote!(
+            impl #vexillo::internal::proptest::arbitrary::Arbitrary for #ty {
+                type Parameters = ();
+                type Strategy = #vexillo::internal::proptest::strategy::BoxedStrategy<Self>;
+                
+                fn arbitrary_with(_: ()) -> Self::Strategy {
+                    #vexillo::proptest::any_valid()
+                }
+            }
+        ));
+    }
+    if cfg!(feature = "arbitrary") {
+        tokens.extend(quote!(
+            impl<'a> #vexillo::internal::arbitrary::Arbitrary<'a> for #ty {
+                fn arbitrary(u: &mut #vexillo::internal::arbitrary::Unstructured<'a>) -> #vexillo::internal::arbitrary::Result<Self> {
+                    #vexillo::arbitrary::any_valid(u)
+                }
+                
+                #[inline]
+                fn size_hint(_depth: usize) -> (usize, ::core::option::Option<usize>) {
+                    #vexillo::arbitrary::size_hint::<Self>()
+                }
+            }
+        ));
+    }
+    tokens
+}
+
+fn build_exclusive_impls(input: &FlagsInput) -> proc_macro2::TokenStream {
+    let exclusive = &input.consts.exclusive;
+    if exclusive.is_empty() {
+        return proc_macro2::TokenStream::new();
+    }
+    let ty = input.type_name();
+    let set_count = exclusive.len();
+    let sets = exclusive.iter().map(|set| {
+        let indices = &set.indices;
+        quote!({
+            let mut set = #ty::NONE;
+            #(set.__vexillo_set_bit(#indices, true);)*
+            set
+        })
+    });
+    quote!(
+        impl #ty {
+            /// The single flags of each exclusive set, in declaration order.
+            /// At most one flag of each set may be set.
+            pub const EXCLUSIVE_SETS: [#ty; #set_count] = [#(#sets),*];
+        }
+    )
+}
+
+fn build_implication_impls(input: &FlagsInput) -> proc_macro2::TokenStream {
+    let implications = &input.consts.implications;
+    if implications.is_empty() {
+        return proc_macro2::TokenStream::new();
+    }
+    let ty = input.type_name();
+    let count = implications.len();
+    let rows = implications.iter().map(|implication| {
+        let index = implication.index;
+        let implied = implication.implied.ones();
+        quote!((
+            {
+                let mut flag = #ty::NONE;
+                flag.__vexillo_set_bit(#index, true);
+                flag
+            },
+            {
+                let mut implied = #ty::NONE;
+                #(implied.__vexillo_set_bit(#implied, true);)*
+                implied
+            },
+        ))
+    });
+    quote!(
+        impl #ty {
+            /// Each single flag that implies other flags, and every flag that it implies,
+            /// directly or through other flags. Ordered by bit index.
+            pub const IMPLICATIONS: [(#ty, #ty); #count] = [#(#rows),*];
+        }
+    )
+}
+
+fn build_atomic_impls(input: &FlagsInput) -> proc_macro2::TokenStream {
+    let Some(options) = &input.options.atomic else {
+        return proc_macro2::TokenStream::new();
+    };
+    let ty = input.type_name();
+    let vexillo = &input.vexillo_crate;
+    let TypeDef { vis, mask_type, storage, .. } = &input.type_def;
+    let atomic = options.name
+        .clone()
+        .unwrap_or_else(|| format_ident!("Atomic{}", ty.unraw()));
+    // Converts between the flags type and its words for either kind of storage.
+    let to_words = |flags: proc_macro2::TokenStream| match storage {
+        Storage::Array => quote!(#flags.masks),
+        Storage::Integer => quote!([#flags.bits]),
+    };
+    let from_words = |words: proc_macro2::TokenStream| match storage {
+        Storage::Array => quote!(#ty { masks: #words }),
+        Storage::Integer => quote!(#ty { bits: #words[0] }),
+    };
+    let new_words = to_words(quote!(flags));
+    let current_words = to_words(quote!(current));
+    let replace_words = to_words(quote!(new));
+    let from_inner = from_words(quote!(words));
+    let from_previous = from_words(quote!(previous));
+    let ordering = quote!(::core::sync::atomic::Ordering);
+    let atomic_mask = quote!(<#mask_type as #vexillo::internal::AtomicMask>);
+    let doc = format!("An atomic [{ty}] that can be shared between threads.\n\nEach mask is its own atomic word, so operations on flags that span more than one mask are only atomic per word. Other threads can observe some words of a store or a fetch operation before the rest.");
+    let fetch = |name: &str, doc: &str, op: &str, invert: bool| {
+        let name = format_ident!("{name}");
+        let op = format_ident!("{op}");
+        let word = if invert {
+            quote!(!words[i])
+        } else {
+            quote!(words[i])
+        };
+        quote!(
+            #[doc = #doc]
+            #[doc = ""]
+            #[doc = "Returns the previous flags."]
+            #[inline]
+            pub fn #name(&self, flags: #ty, order: #ordering) -> #ty {
+                let words = #new_words;
+                let mut previous = [0; #ty::MASK_COUNT];
+                for i in 0..#ty::MASK_COUNT {
+                    previous[i] = #atomic_mask::#op(&self.masks[i], #word, order);
+                }
+                #from_previous
+            }
+        )
+    };
+    let fetch_add = fetch("fetch_add", "Set the bits of `flags`.", "fetch_or", false);
+    let fetch_remove = fetch("fetch_remove", "Clear the bits of `flags`.", "fetch_and", true);
+    let fetch_toggle = fetch("fetch_toggle", "Toggle the bits of `flags`.", "fetch_xor", false);
+    quote!(
+        #[doc = #doc]
+        #[repr(transparent)]
+        #vis struct #atomic {
+            masks: [#atomic_mask::Atomic; #ty::MASK_COUNT],
+        }
+        
+        impl #atomic {
+            /// Create a new atomic holding `flags`.
+            #[inline]
+            #[must_use]
+            pub const fn new(flags: #ty) -> Self {
+                Self {
+                    // SAFETY: The atomic integers have the same in-memory representation as the mask type.
+                    masks: unsafe { ::core::mem::transmute(#new_words) },
+                }
+            }
+            
+            /// Consume the atomic and return the flags.
+            #[inline]
+            #[must_use]
+            pub const fn into_inner(self) -> #ty {
+                // SAFETY: The atomic integers have the same in-memory representation as the mask type.
+                let words: [#mask_type; #ty::MASK_COUNT] = unsafe { ::core::mem::transmute(self.masks) };
+                #from_inner
+            }
+            
+            /// Load the flags.
+            /// 
+            /// Panics if `order` is [Release](::core::sync::atomic::Ordering::Release) or [AcqRel](::core::sync::atomic::Ordering::AcqRel).
+            #[inline]
+            #[must_use]
+            pub fn load(&self, order: #ordering) -> #ty {
+                let mut words = [0; #ty::MASK_COUNT];
+                for i in 0..#ty::MASK_COUNT {
+                    words[i] = #atomic_mask::load(&self.masks[i], order);
+                }
+                #from_inner
+            }
+            
+            /// Store `flags`.
+            /// 
+            /// Panics if `order` is [Acquire](::core::sync::atomic::Ordering::Acquire) or [AcqRel](::core::sync::atomic::Ordering::AcqRel).
+            #[inline]
+            pub fn store(&self, flags: #ty, order: #ordering) {
+                let words = #new_words;
+                for i in 0..#ty::MASK_COUNT {
+                    #atomic_mask::store(&self.masks[i], words[i], order);
+                }
+            }
+            
+            #fetch_add
+            
+            #fetch_remove
+            
+            #fetch_toggle
+            
+            /// Store `new` if the flags are `current`.
+            /// 
+            /// Returns the previous flags, which are `current` on success.
+            /// 
+            /// The words are compared and exchanged one at a time. If a word does not match,
+            /// the words that were already exchanged are exchanged back (unless another thread
+            /// changed them in the meantime), and the flags that were observed are returned as the error.
+            /// 
+            /// This is only a single atomic operation when there is one mask word. With more words
+            /// it is atomic per word but not linearizable: other threads can observe some words
+            /// exchanged and others not, including words that are exchanged back after a mismatch.
+            pub fn compare_exchange(
+                &self,
+                current: #ty,
+                new: #ty,
+                success: #ordering,
+                failure: #ordering,
+            ) -> ::core::result::Result<#ty, #ty> {
+                let current_words = #current_words;
+                let new_words = #replace_words;
+                let mut previous = current_words;
+                for i in 0..#ty::MASK_COUNT {
+                    let Err(actual) = #atomic_mask::compare_exchange(&self.masks[i], current_words[i], new_words[i], success, failure) else {
+                        continue;
+                    };
+                    previous[i] = actual;
+                    for j in 0..i {
+                        _=#atomic_mask::compare_exchange(&self.masks[j], new_words[j], current_words[j], success, failure);
+                    }
+                    for j in i + 1..#ty::MASK_COUNT {
+                        previous[j] = #atomic_mask::load(&self.masks[j], failure);
+                    }
+                    return ::core::result::Result::Err(#from_previous);
+                }
+                ::core::result::Result::Ok(#from_previous)
+            }
+            
+            /// Test if all bits of `flags` are set. Each word is loaded on its own.
+            #[inline]
+            #[must_use]
+            pub fn has_all(&self, flags: #ty, order: #ordering) -> bool {
+                let words = #new_words;
+                (0..#ty::MASK_COUNT).all(|i| #atomic_mask::load(&self.masks[i], order) & words[i] == words[i])
+            }
+            
+            /// Test if any bit of `flags` is set. Each word is loaded on its own.
+            #[inline]
+            #[must_use]
+            pub fn has_any(&self, flags: #ty, order: #ordering) -> bool {
+                let words = #new_words;
+                (0..#ty::MASK_COUNT).any(|i| #atomic_mask::load(&self.masks[i], order) & words[i] != 0)
+            }
+        }
+        
+        impl ::core::default::Default for #atomic {
+            #[inline]
+            fn default() -> Self {
+                Self::new(#ty::NONE)
+            }
+        }
+        
+        impl ::core::convert::From<#ty> for #atomic {
+            #[inline]
+            fn from(flags: #ty) -> Self {
+                Self::new(flags)
+            }
+        }
+        
+        impl ::core::fmt::Debug for #atomic {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Debug::fmt(&self.load(::core::sync::atomic::Ordering::Relaxed), f)
+            }
+        }
+    )
+}
+
+fn build_flag_enum_impls(input: &FlagsInput) -> proc_macro2::TokenStream {
+    let Some(FlagEnum { name, variants }) = &input.flag_enum else {
+        return proc_macro2::TokenStream::new();
+    };
+    let ty = input.type_name();
+    let vexillo = &input.vexillo_crate;
+    let vis = &input.type_def.vis;
+    let mut rows = input.consts.singles.iter().zip(variants).collect::<Vec<_>>();
+    rows.sort_unstable_by_key(|(single, _)| single.index);
+    let (singles, variants): (Vec<_>, Vec<_>) = rows.into_iter().unzip();
+    let idents = singles.iter().map(|single| &single.ident).collect::<Vec<_>>();
+    let names = idents.iter().map(|ident| ident.unraw().to_string()).collect::<Vec<_>>();
+    // Unsuffixed so that they can be discriminants of either repr.
+    let indices = singles.iter()
+        .map(|single| proc_macro2::Literal::u32_unsuffixed(single.index))
+        .collect::<Vec<_>>();
+    let docs = singles.iter()
+        .map(|single| single.attrs.iter().filter(|attr| attr.path().is_ident("doc")).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let variant_count = variants.len();
+    // Pinned flags can have indices past `u16::MAX`.
+    let repr = if input.consts.bit_count() <= u16::MAX as u32 + 1 {
+        quote!(u16)
+    } else {
+        quote!(u32)
+    };
+    let doc = format!("A single flag of [{ty}].\n\nThe discriminant of each variant is the bit index of its flag.");
+    quote!(
+        #[doc = #doc]
+        #[repr(#repr)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #vis enum #name {
+            #(
+                #(#docs)*
+                #variants = #indices,
+            )*
+        }
+        
+        // `flags` uses flags that may be `#[deprecated]`.
+        #[allow(deprecated)]
+        impl #name {
+            /// Every variant, in bit index order.
+            pub const ALL_VARIANTS: [#name; #variant_count] = [#(#name::#variants),*];
+            
+            /// The bit index of the flag.
+            #[inline]
+            #[must_use]
+            pub const fn index(self) -> u32 {
+                self as u32
+            }
+            
+            /// The name of the flag, such as `BAN_USER`.
+            #[must_use]
+            pub const fn name(self) -> &'static str {
+                match self {
+                    #(#name::#variants => #names,)*
+                }
+            }
+            
+            /// The flags value of the flag.
+            #[must_use]
+            pub const fn flags(self) -> #ty {
+                match self {
+                    #(#name::#variants => #ty::#idents,)*
+                }
+            }
+            
+            /// The variant of the single flag at bit `index`, if there is one.
+            #[must_use]
+            pub const fn from_index(index: u32) -> ::core::option::Option<Self> {
+                match index {
+                    #(#indices => ::core::option::Option::Some(#name::#variants),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+        
+        impl ::core::convert::From<#name> for #ty {
+            #[inline]
+            fn from(flag: #name) -> Self {
+                flag.flags()
+            }
+        }
+        
+        impl ::core::convert::TryFrom<#ty> for #name {
+            type Error = #vexillo::NotSingleFlagError;
+            
+            /// Succeeds if `flags` is exactly one single flag.
+            /// Bits that are not part of any flag make the conversion fail.
+            fn try_from(flags: #ty) -> ::core::result::Result<Self, Self::Error> {
+                // `iter_indices` skips unknown bits, so the value is compared in full.
+                match #vexillo::Flags::iter_indices(flags).next().and_then(#name::from_index) {
+                    ::core::option::Option::Some(flag) if #vexillo::Flags::eq(flag.flags(), flags) => {
+                        ::core::result::Result::Ok(flag)
+                    }
+                    _ => ::core::result::Result::Err(#vexillo::NotSingleFlagError),
+                }
+            }
+        }
+        
+        impl ::core::ops::BitOr for #name {
+            type Output = #ty;
+            #[inline]
+            fn bitor(self, rhs: Self) -> #ty {
+                self.flags() | rhs.flags()
+            }
+        }
+        
+        impl ::core::ops::BitOr<#ty> for #name {
+            type Output = #ty;
+            #[inline]
+            fn bitor(self, rhs: #ty) -> #ty {
+                self.flags() | rhs
+            }
+        }
+        
+        impl ::core::ops::BitOr<#name> for #ty {
+            type Output = #ty;
+            #[inline]
+            fn bitor(self, rhs: #name) -> #ty {
+                self | rhs.flags()
+            }
+        }
+    )
+}
+
+fn build_flags_trait_impl(input: &FlagsInput) -> syn::File {
+    let ty = input.type_name();
+    let vexillo = &input.vexillo_crate;
+    let mask_ty = &input.type_def.mask_type;
+    let mut trait_impl: syn::File = syn::parse_quote!(
+        impl #vexillo::Flags for #ty {
+            type MaskType = #mask_ty;
+            type MasksArrayType = [#mask_ty; #ty::MASK_COUNT];
+            type BytesArrayType = [u8; ::core::mem::size_of::<#ty>()];
+            type MapArrayType<V> = [Option<V>; #ty::SINGLE_FLAG_COUNT];
+            
+            const BITS: u32 = #ty::BITS;
+            const USED_BITS: u32 = #ty::USED_BITS;
+            const UNUSED_BITS: u32 = #ty::UNUSED_BITS;
+            const INDEX_SPAN: u32 = #ty::INDEX_SPAN;
+            const MASK_BITS: u32 = #ty::MASK_BITS;
+            const MASK_SIZE: usize = #ty::MASK_SIZE;
+            const MASK_COUNT: usize = #ty::MASK_COUNT;
+            
+            const SINGLE_FLAG_COUNT: usize = #ty::SINGLE_FLAG_COUNT;
+            const GROUP_FLAG_COUNT: usize = #ty::GROUP_FLAG_COUNT;
+            const TOTAL_FLAG_COUNT: usize = #ty::TOTAL_FLAG_COUNT;
+            const NONE: Self = #ty::NONE;
+            const ALL: Self = #ty::ALL;
+            
+            const FLAGS_TABLE: &'static [#vexillo::FlagRow<Self>] = &#ty::TABLES.rows;
+            const SINGLE_FLAGS: &'static [#vexillo::FlagRow<Self>] = #ty::TABLES.singles().rows;
+            const GROUP_FLAGS: &'static [#vexillo::FlagRow<Self>] = #ty::TABLES.groups().rows;
+            const BIT_ORDERED_FLAGS_TABLE: &'static [#vexillo::FlagIndex] = &#ty::TABLES.bit_ordered_row_indices;
+            const ORDERED_FLAGS_TABLE: &'static [#vexillo::FlagIndex] = &#ty::TABLES.name_ordered_row_indices;
+            const ORDERED_SINGLE_FLAG_INDICES: &'static [#vexillo::FlagIndex] = &#ty::TABLES.name_ordered_single_indices;
+            const ORDERED_GROUP_FLAG_INDICES: &'static [#vexillo::FlagIndex] = &#ty::TABLES.name_ordered_group_indices;
+            const ALIASES: &'static [#vexillo::FlagAlias] = #ty::TABLES.aliases;
+            
+            #[inline(always)]
+            fn new() -> Self {
+                Self::new()
+            }
+            
+            #[inline(always)]
+            fn none() -> Self {
+                Self::none()
+            }
+            
+            #[inline(always)]
+            fn all() -> Self {
+                Self::all()
+            }
+            
+            #[inline(always)]
+            fn union(flags: &[Self]) -> Self {
+                Self::union(flags)
+            }
+            
+            #[inline(always)]
+            fn union_without(flags: &[Self], removals: &[Self]) -> Self {
+                Self::union_without(flags, removals)
+            }
+            
+            #[inline(always)]
+            fn try_find(name: &str) -> Option<Self> {
+                Self::try_find(name)
+            }
+            
+            #[inline(always)]
+            #[track_caller]
+            fn find(name: &str) -> Self {
+                Self::find(name)
+            }
+            
+            #[inline(always)]
+            fn find_or(name: &str, default: Self) -> Self {
+                Self::find_or(name, default)
+            }
+            
+            #[inline(always)]
+            fn find_or_none(name: &str) -> Self {
+                Self::find_or_none(name)
+            }
+            
+            #[inline(always)]
+            fn map_array_from_fn<V, Init: FnMut(usize) -> Option<V>>(init: Init) -> [Option<V>; #ty::SINGLE_FLAG_COUNT] {
+                ::core::array::from_fn(init)
+            }
+            
+            #[inline(always)]
+            fn count_ones(self) -> u32 {
+                Self::count_ones(self)
+            }
+            
+            #[inline(always)]
+            fn count_zeros(self) -> u32 {
+                Self::count_zeros(self)
+            }
+            
+            #[inline(always)]
+            fn get(self, index: u32) -> bool {
+                Self::get(self, index)
+            }
+            
+            #[inline(always)]
+            fn set(&mut self, index: u32, on: bool) -> &mut Self {
+                Self::set(self, index, on)
+            }
+            
+            #[inline(always)]
+            fn swap(&mut self, index: u32, on: bool) -> bool {
+                Self::swap(self, index, on)
+            }
+            
+            #[inline(always)]
+            fn from_index(index: u32) -> Self {
+                Self::from_index(index)
+            }
+            
+            #[inline(always)]
+            fn add(&mut self, flag: Self) -> &mut Self {
+                Self::add(self, flag)
+            }
+            
+            #[inline(always)]
+            fn add_all(&mut self, flags: &[Self]) -> &mut Self {
+                Self::add_all(self, flags)
+            }
+            
+            #[inline(always)]
+            fn remove(&mut self, flag: Self) -> &mut Self {
+                Self::remove(self, flag)
+            }
+            
+            #[inline(always)]
+            fn remove_all(&mut self, flags: &[Self]) -> &mut Self {
+                Self::remove_all(self, flags)
+            }
+            
+            #[inline(always)]
+            fn with(self, flag: Self) -> Self {
+                Self::with(self, flag)
+            }
+            
+            #[inline(always)]
+            fn with_all(self, flags: &[Self]) -> Self {
+                Self::with_all(self, flags)
+            }
+            
+            #[inline(always)]
+            fn without(self, flag: Self) -> Self {
+                Self::without(self, flag)
+            }
+            
+            #[inline(always)]
+            fn without_all(self, flags: &[Self]) -> Self {
+                Self::without_all(self, flags)
+            }
+            
+            #[inline(always)]
+            fn has_all(self, flag: Self) -> bool {
+                Self::has_all(self, flag)
+            }
+            
+            #[inline(always)]
+            fn has_none(self, flag: Self) -> bool {
+                Self::has_none(self, flag)
+            }
+            
+            #[inline(always)]
+            fn has_any(self, flag: Self) -> bool {
+                Self::has_any(self, flag)
+            }
+            
+            #[inline(always)]
+            fn as_slice(&self) -> &[#mask_ty] {
+                Self::as_slice(self)
+            }
+            
+            #[inline(always)]
+            fn as_mut_slice(&mut self) -> &mut [#mask_ty] {
+                Self::as_mut_slice(self)
+            }
+            
+            #[inline(always)]
+            fn into_inner(self) -> [#mask_ty; #ty::MASK_COUNT] {
+                Self::into_inner(self)
+            }
+            
+            #[inline(always)]
+            fn as_bytes(&self) -> &[u8] {
+                Self::as_bytes(self)
+            }
+            
+            #[inline(always)]
+            fn as_mut_bytes(&mut self) -> &mut [u8] {
+                Self::as_mut_bytes(self)
+            }
+            
+            #[inline(always)]
+            fn to_be_bytes(self) -> [u8; ::core::mem::size_of::<#ty>()] {
+                Self::to_be_bytes(self)
+            }
+            
+            #[inline(always)]
+            fn from_be_bytes(bytes: [u8; ::core::mem::size_of::<#ty>()]) -> Self {
+                Self::from_be_bytes(bytes)
+            }
+            
+            #[inline(always)]
+            fn to_le_bytes(self) -> [u8; ::core::mem::size_of::<#ty>()] {
+                Self::to_le_bytes(self)
+            }
+            
+            #[inline(always)]
+            fn from_le_bytes(bytes: [u8; ::core::mem::size_of::<#ty>()]) -> Self {
+                Self::from_le_bytes(bytes)
+            }
+            
+            #[inline(always)]
+            fn to_ne_bytes(self) -> [u8; ::core::mem::size_of::<#ty>()] {
+                Self::to_ne_bytes(self)
+            }
+            
+            #[inline(always)]
+            fn from_ne_bytes(bytes: [u8; ::core::mem::size_of::<#ty>()]) -> Self {
+                Self::from_ne_bytes(bytes)
+            }
+            
+            #[inline(always)]
+            fn not(self) -> Self {
+                Self::not(self)
+            }
+            
+            #[inline(always)]
+            fn and(self, other: Self) -> Self {
+                Self::and(self, other)
+            }
+            
+            #[inline(always)]
+            fn or(self, other: Self) -> Self {
+                Self::or(self, other)
+            }
+            
+            #[inline(always)]
+            fn xor(self, other: Self) -> Self {
+                Self::xor(self, other)
+            }
+            
+            #[inline(always)]
+            fn nand(self, other: Self) -> Self {
+                Self::nand(self, other)
+            }
+            
+            #[inline(always)]
+            fn nor(self, other: Self) -> Self {
+                Self::nor(self, other)
+            }
+            
+            #[inline(always)]
+            fn xnor(self, other: Self) -> Self {
+                Self::xnor(self, other)
+            }
+            
+            #[inline(always)]
+            fn imply(self, other: Self) -> Self {
+                Self::imply(self, other)
+            }
+            
+            #[inline(always)]
+            fn nimply(self, other: Self) -> Self {
+                Self::nimply(self, other)
+            }
+            
+            #[inline(always)]
+            fn eq(self, other: Self) -> bool {
+                Self::eq(self, other)
+            }
+            
+            #[inline(always)]
+            fn ne(self, other: Self) -> bool {
+                Self::ne(self, other)
+            }
+            
+            #[inline(always)]
+            fn iter(self) -> #vexillo::Iter<Self> {
+                Self::iter(self)
+            }
+            
+            #[inline(always)]
+            fn iter_indices(self) -> #vexillo::IterIndices<Self> {
+                Self::iter_indices(self)
+            }
+            
+            #[inline(always)]
+            fn iter_names(self) -> #vexillo::IterNames<Self> {
+                Self::iter_names(self)
+            }
+            
+            #[inline(always)]
+            fn iter_groups(self) -> #vexillo::IterGroups<Self> {
+                Self::iter_groups(self)
+            }
+            
+            #[inline(always)]
+            fn decompose_named(self) -> #vexillo::DecomposeNamed<Self> {
+                Self::decompose_named(self)
+            }
+        }
+    );
+    let mut overrider = Overrider {
+        overrides: &input.config,
+        stage: OverrideStage::FlagsTrait,
+    };
+    overrider.visit_file_mut(&mut trait_impl);
+    trait_impl
+}
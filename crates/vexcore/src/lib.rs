@@ -1,15 +1,40 @@
 use syn::{Attribute, spanned::Spanned};
 
 pub mod bitmask;
+pub mod c_header;
 pub mod const_block;
+pub mod dep_graph;
 pub mod flags_input;
+pub mod lint;
+pub mod lock;
+pub mod options;
 pub mod override_block;
 pub mod type_def;
 pub mod vis;
-// TODO
-// mod dep_graph;
 
-pub const FLAG_CFG_ERR_MSG: &'static str = "`cfg` attribute is error prone and is not allowed.\nInstead, use `cfg` on the macro call itself.\n\nDenying `cfg` attributes keeps the flags consistent across versions.\nAttempts to circumvent this error is likely to result in undesireable consequences.";
+/// Collects errors so that they can all be reported at once.
+#[derive(Default)]
+pub struct Errors(Option<syn::Error>);
+
+impl Errors {
+    /// Add `err` to the collected errors.
+    pub fn push(&mut self, err: syn::Error) {
+        match &mut self.0 {
+            Some(error) => error.combine(err),
+            None => self.0 = Some(err),
+        }
+    }
+    
+    /// `Ok(value)` if nothing was collected, or else all of the collected errors.
+    pub fn finish<T>(self, value: T) -> syn::Result<T> {
+        match self.0 {
+            Some(error) => Err(error),
+            None => Ok(value),
+        }
+    }
+}
+
+pub const FLAG_CFG_ERR_MSG: &str = "`cfg` attribute is error prone and is not allowed.\nInstead, use `cfg` on the macro call itself.\n\nDenying `cfg` attributes keeps the flags consistent across versions.\nAttempts to circumvent this error is likely to result in undesireable consequences.";
 
 pub fn verify_no_cfg<'a, It: IntoIterator<Item = &'a Attribute>, M: std::fmt::Display>(attrs: It, message: M) -> syn::Result<()> {
     attrs.into_iter().try_for_each(move |attr| {
@@ -0,0 +1,140 @@
+use proc_macro2::Span;
+use quote::{format_ident, quote_spanned, ToTokens};
+use syn::{Attribute, Path, Token, punctuated::Punctuated};
+
+use crate::Errors;
+
+/// Warnings for suspicious group definitions.
+///
+/// A lint is silenced on a group (and the groups declared in it) with `#[allow(vexillo::name)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lint {
+    /// `+ X` adds nothing new to the group, or `- X` removes nothing from it.
+    Redundant,
+    /// The group has the same flags as a group declared before it.
+    Duplicate,
+    /// The group has no flags.
+    Empty,
+}
+
+impl Lint {
+    const LINTS: [Lint; 3] = [
+        Lint::Redundant,
+        Lint::Duplicate,
+        Lint::Empty,
+    ];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Lint::Redundant => "redundant",
+            Lint::Duplicate => "duplicate",
+            Lint::Empty => "empty",
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// The lints that are allowed on a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllowedLints(u8);
+
+impl AllowedLints {
+    #[inline]
+    #[must_use]
+    pub const fn allows(self, lint: Lint) -> bool {
+        self.0 & lint.bit() != 0
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Removes the `vexillo::` lints from the `#[allow(...)]` attributes in `attrs` and collects them.
+    ///
+    /// Other lints in the same attribute are kept. All errors are combined.
+    pub fn extract(attrs: &mut Vec<Attribute>) -> syn::Result<Self> {
+        let mut allowed = Self::default();
+        let mut errors = Errors::default();
+        attrs.retain_mut(|attr| {
+            if !attr.path().is_ident("allow") {
+                return true;
+            }
+            // Leave anything that isn't a list of lint paths to the compiler.
+            let Ok(lints) = attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated) else {
+                return true;
+            };
+            let mut rest = Punctuated::<Path, Token![,]>::new();
+            for path in lints {
+                let is_vexillo = path.segments.len() == 2 && path.segments[0].ident == "vexillo";
+                if !is_vexillo {
+                    rest.push(path);
+                    continue;
+                }
+                let name = &path.segments[1].ident;
+                match Lint::LINTS.iter().find(|lint| name == lint.name()) {
+                    Some(&lint) => allowed.0 |= lint.bit(),
+                    None => {
+                        let expected = Lint::LINTS.iter()
+                            .map(|lint| format!("`vexillo::{}`", lint.name()))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        errors.push(syn::Error::new(name.span(), format!("Unknown vexillo lint `{name}`. Expected one of {expected}.")));
+                    }
+                }
+            }
+            if rest.is_empty() {
+                return false;
+            }
+            *attr = syn::parse_quote!(#[allow(#rest)]);
+            true
+        });
+        errors.finish(allowed)
+    }
+}
+
+/// A lint warning for a span in the macro input.
+pub struct Warning {
+    pub lint: Lint,
+    pub span: Span,
+    pub message: String,
+}
+
+impl Warning {
+    #[must_use]
+    pub fn new<M: Into<String>>(lint: Lint, span: Span, message: M) -> Self {
+        Self {
+            lint,
+            span,
+            message: message.into(),
+        }
+    }
+}
+
+impl ToTokens for Warning {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        // Proc macros can't emit warnings on stable, but the compiler will
+        // warn about a deprecated item that is used at the offending span.
+        let name = format_ident!("vexillo_{}", self.lint.name(), span = self.span);
+        let note = format!(
+            "{} Add `#[allow(vexillo::{})]` to the group to silence this warning.",
+            self.message,
+            self.lint.name(),
+        );
+        tokens.extend(quote_spanned!(self.span=>
+            const _: () = {
+                #[deprecated(note = #note)]
+                #[allow(non_upper_case_globals)]
+                const #name: () = ();
+                #name
+            };
+        ));
+    }
+}
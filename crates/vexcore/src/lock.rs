@@ -0,0 +1,148 @@
+//! The lockfile that records the bit index of every single flag, requested with
+//! `#[vexillo(lock = "perms.lock")]`.
+//!
+//! The file has one `NAME = INDEX` line per single flag, ordered by index. Lines
+//! starting with `#` are comments.
+//!
+//! Flags in the lockfile keep their recorded index, and new flags are appended after
+//! the highest recorded index. A recorded flag that is no longer declared, or that is
+//! pinned to a different index, is an error unless the `VEXILLO_UPDATE_LOCK` environment
+//! variable is set. So is a single flag that is not recorded, because its appended index
+//! would change if another flag was added in front of it before it was recorded.
+//!
+//! The lockfile is only written while `VEXILLO_UPDATE_LOCK` is set, so that expanding the
+//! macro (for example in an editor) never touches the source tree.
+
+use std::{fmt::Write, path::PathBuf};
+
+use proc_macro2::Span;
+use syn::LitStr;
+
+/// The environment variable that allows the lockfile to be written, and to add, drop and move flags.
+pub const UPDATE_ENV: &str = "VEXILLO_UPDATE_LOCK";
+
+pub struct Lockfile {
+    /// The path as written in the attribute, for messages.
+    pub display_path: String,
+    pub path: PathBuf,
+    pub span: Span,
+    /// The recorded names and indices, in file order.
+    pub entries: Vec<(String, u32)>,
+    /// The file contents, or `None` if there is no file yet.
+    pub text: Option<String>,
+    /// New, removed and moved flags are accepted, and the file is written.
+    pub update: bool,
+}
+
+impl Lockfile {
+    /// Read the lockfile at `path`, relative to the directory of the crate that is being built.
+    pub fn load(path: &LitStr) -> syn::Result<Self> {
+        let display_path = path.value();
+        let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+            .ok_or_else(|| syn::Error::new(path.span(), "`CARGO_MANIFEST_DIR` is not set, so the lockfile can't be found."))?;
+        let full_path = PathBuf::from(manifest_dir).join(&display_path);
+        let text = match std::fs::read_to_string(&full_path) {
+            Ok(text) => Some(text),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(syn::Error::new(path.span(), format!("Failed to read the lockfile `{display_path}`: {err}"))),
+        };
+        let entries = match &text {
+            Some(text) => Self::parse(text).map_err(|message| {
+                syn::Error::new(path.span(), format!("Failed to parse the lockfile `{display_path}`: {message}"))
+            })?,
+            None => Vec::new(),
+        };
+        let update = std::env::var_os(UPDATE_ENV).is_some_and(|value| !value.is_empty() && value != "0");
+        Ok(Self {
+            display_path,
+            path: full_path,
+            span: path.span(),
+            entries,
+            text,
+            update,
+        })
+    }
+
+    fn parse(text: &str) -> Result<Vec<(String, u32)>, String> {
+        let mut entries = Vec::<(String, u32)>::new();
+        for (line_index, line) in text.lines().enumerate() {
+            let line_number = line_index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((name, index)) = line.split_once('=') else {
+                return Err(format!("Expected `NAME = INDEX` on line {line_number}."));
+            };
+            let name = name.trim();
+            let index = index.trim()
+                .parse::<u32>()
+                .map_err(|_| format!("Expected a bit index after `=` on line {line_number}."))?;
+            if entries.iter().any(|(other, _)| other == name) {
+                return Err(format!("`{name}` is recorded more than once (line {line_number})."));
+            }
+            if let Some((other, _)) = entries.iter().find(|&&(_, other)| other == index) {
+                return Err(format!("`{name}` and `{other}` are both recorded at bit {index} (line {line_number})."));
+            }
+            entries.push((name.to_owned(), index));
+        }
+        Ok(entries)
+    }
+
+    /// The recorded index of the flag `name`.
+    #[must_use]
+    pub fn index_of(&self, name: &str) -> Option<u32> {
+        self.entries.iter()
+            .find(|(recorded, _)| recorded == name)
+            .map(|&(_, index)| index)
+    }
+
+    /// The first index after every recorded index, where new flags are appended.
+    #[must_use]
+    pub fn next_index(&self) -> u32 {
+        self.entries.iter()
+            .map(|&(_, index)| index + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The lockfile contents for `flags`, which are the names and indices of the single flags.
+    #[must_use]
+    pub fn render(type_name: &str, flags: &[(String, u32)]) -> String {
+        let mut flags = flags.iter().collect::<Vec<_>>();
+        flags.sort_unstable_by_key(|&&(_, index)| index);
+        let mut text = String::new();
+        // `write!` to a `String` can't fail.
+        _=writeln!(text, "# The bit indices of the flags of `{type_name}`, maintained by vexillo.");
+        _=writeln!(text, "# Set `{UPDATE_ENV}=1` while building to record new flags, or to remove or move flags.");
+        for (name, index) in flags {
+            _=writeln!(text, "{name} = {index}");
+        }
+        text
+    }
+
+    /// Write the lockfile for `flags` if `VEXILLO_UPDATE_LOCK` is set and it is different from the file.
+    pub fn save(&self, type_name: &str, flags: &[(String, u32)]) -> syn::Result<()> {
+        if !self.update {
+            return Ok(());
+        }
+        let text = Self::render(type_name, flags);
+        if self.text.as_deref() == Some(text.as_str()) {
+            return Ok(());
+        }
+        std::fs::write(&self.path, text).map_err(|err| {
+            syn::Error::new(self.span, format!("Failed to write the lockfile `{}`: {err}", self.display_path))
+        })
+    }
+}
+
+#[test]
+fn lockfile_parse_test() {
+    let text = Lockfile::render("Perms", &[("KICK".into(), 9), ("BAN_USER".into(), 0)]);
+    assert_eq!(text.lines().skip(2).collect::<Vec<_>>(), ["BAN_USER = 0", "KICK = 9"]);
+    assert_eq!(Lockfile::parse(&text), Ok(vec![("BAN_USER".into(), 0), ("KICK".into(), 9)]));
+    assert!(Lockfile::parse("BAN_USER = 0\nKICK = 0").is_err());
+    assert!(Lockfile::parse("BAN_USER = 0\nBAN_USER = 1").is_err());
+    assert!(Lockfile::parse("BAN_USER").is_err());
+    assert!(Lockfile::parse("BAN_USER = x").is_err());
+}
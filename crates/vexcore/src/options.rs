@@ -0,0 +1,185 @@
+use syn::{Attribute, Ident, LitStr, Token, meta::ParseNestedMeta};
+
+use crate::Errors;
+
+/// How the `Debug` impl is generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DebugStyle {
+    /// `Perms(BAN_USER | UNBAN_USER)`
+    #[default]
+    Names,
+    /// `#[derive(Debug)]`
+    Raw,
+}
+
+/// How the `Display` impl is generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisplayStyle {
+    /// `BAN_USER | UNBAN_USER`
+    #[default]
+    Names,
+    /// Like [DisplayStyle::Names], but fully covered groups are named instead of their flags.
+    Groups,
+    /// No `Display` impl is generated.
+    None,
+}
+
+/// How flags are represented by the generated `Serialize`/`Deserialize` impls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SerdeRepr {
+    /// [SerdeRepr::Names] for human-readable formats, [SerdeRepr::Bytes] otherwise.
+    #[default]
+    Auto,
+    /// A list of flag names.
+    Names,
+    /// Little-endian bytes.
+    Bytes,
+    /// The mask integers.
+    Masks,
+}
+
+/// What deserialization does with unknown names and bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SerdeUnknown {
+    #[default]
+    Error,
+    Ignore,
+    Preserve,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SerdeOptions {
+    pub repr: SerdeRepr,
+    pub unknown: SerdeUnknown,
+}
+
+/// A type that is generated alongside the flags type, requested with `option` or `option = Name`.
+#[derive(Debug, Clone)]
+pub struct Companion {
+    /// When not given, the name is derived from the name of the flags type.
+    pub name: Option<Ident>,
+}
+
+impl Companion {
+    fn parse(meta: &ParseNestedMeta) -> syn::Result<Self> {
+        let name = if meta.input.peek(Token![=]) {
+            Some(meta.value()?.parse()?)
+        } else {
+            None
+        };
+        Ok(Self { name })
+    }
+}
+
+/// Options given to the macro with `#[vexillo(...)]` attributes on the type definition.
+///
+/// ```rust,ignore
+/// #[vexillo(debug = raw, display = groups, serde(repr = names, unknown = ignore), atomic, flag_enum = Perm, c_header = perm, lock = "perms.lock")]
+/// pub struct Perms(pub [u8]);
+/// ```
+#[derive(Clone, Default)]
+pub struct FlagsOptions {
+    pub debug: DebugStyle,
+    pub display: DisplayStyle,
+    pub serde: SerdeOptions,
+    /// `AtomicPerms` by default.
+    pub atomic: Option<Companion>,
+    /// `PermsFlag` by default.
+    pub flag_enum: Option<Companion>,
+    /// The C typedef is `perms_t` by default. See [crate::c_header].
+    pub c_header: Option<Companion>,
+    /// The path of the lockfile, relative to the crate directory. See [crate::lock].
+    pub lock: Option<LitStr>,
+}
+
+fn parse_choice<T: Copy>(meta: &ParseNestedMeta, choices: &[(&str, T)]) -> syn::Result<T> {
+    let ident = meta.value()?.parse::<Ident>()?;
+    choices.iter()
+        .find(|(name, _)| ident == name)
+        .map(|&(_, value)| value)
+        .ok_or_else(|| {
+            let expected = choices.iter()
+                .map(|(name, _)| format!("`{name}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            syn::Error::new(ident.span(), format!("Unknown value `{ident}`. Expected one of {expected}."))
+        })
+}
+
+impl FlagsOptions {
+    /// Removes the `#[vexillo(...)]` attributes from `attrs` and parses them.
+    ///
+    /// All errors in all of the attributes are combined.
+    pub fn extract(attrs: &mut Vec<Attribute>) -> syn::Result<Self> {
+        let mut options = Self::default();
+        let mut errors = Errors::default();
+        attrs.retain(|attr| {
+            if !attr.path().is_ident("vexillo") {
+                return true;
+            }
+            let result = attr.parse_nested_meta(|meta| {
+                let result = if meta.path.is_ident("debug") {
+                    parse_choice(&meta, &[
+                        ("names", DebugStyle::Names),
+                        ("raw", DebugStyle::Raw),
+                    ]).map(|style| options.debug = style)
+                } else if meta.path.is_ident("display") {
+                    parse_choice(&meta, &[
+                        ("names", DisplayStyle::Names),
+                        ("groups", DisplayStyle::Groups),
+                        ("none", DisplayStyle::None),
+                    ]).map(|style| options.display = style)
+                } else if meta.path.is_ident("serde") {
+                    if !cfg!(feature = "serde") {
+                        errors.push(meta.error("The `serde` feature of vexillo is not enabled."));
+                    }
+                    meta.parse_nested_meta(|meta| {
+                        let result = if meta.path.is_ident("repr") {
+                            parse_choice(&meta, &[
+                                ("auto", SerdeRepr::Auto),
+                                ("names", SerdeRepr::Names),
+                                ("bytes", SerdeRepr::Bytes),
+                                ("masks", SerdeRepr::Masks),
+                            ]).map(|repr| options.serde.repr = repr)
+                        } else if meta.path.is_ident("unknown") {
+                            parse_choice(&meta, &[
+                                ("error", SerdeUnknown::Error),
+                                ("ignore", SerdeUnknown::Ignore),
+                                ("preserve", SerdeUnknown::Preserve),
+                            ]).map(|unknown| options.serde.unknown = unknown)
+                        } else {
+                            return Err(meta.error("Unknown serde option. Expected `repr` or `unknown`."));
+                        };
+                        if let Err(err) = result {
+                            errors.push(err);
+                        }
+                        Ok(())
+                    })
+                } else if meta.path.is_ident("atomic") {
+                    Companion::parse(&meta).map(|atomic| options.atomic = Some(atomic))
+                } else if meta.path.is_ident("flag_enum") {
+                    Companion::parse(&meta).map(|flag_enum| options.flag_enum = Some(flag_enum))
+                } else if meta.path.is_ident("c_header") {
+                    Companion::parse(&meta).map(|c_header| options.c_header = Some(c_header))
+                } else if meta.path.is_ident("lock") {
+                    meta.value()
+                        .and_then(|value| value.parse::<LitStr>())
+                        .map(|path| options.lock = Some(path))
+                } else {
+                    // The rest of the attribute can't be parsed without knowing the option.
+                    return Err(meta.error("Unknown vexillo option."));
+                };
+                // Keep going so that every bad option is reported.
+                if let Err(err) = result {
+                    errors.push(err);
+                }
+                Ok(())
+            });
+            if let Err(err) = result {
+                errors.push(err);
+            }
+            false
+        });
+        errors.finish(options)
+    }
+}
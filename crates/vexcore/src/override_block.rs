@@ -55,7 +55,7 @@ pub struct OverrideItem {
     pub new_ident: Option<Ident>,
 }
 
-/// 
+/// The builtin items and their user overrides.
 pub struct OverrideBlock {
     pub items: HashMap<Ident, OverrideItem>,
 }
@@ -108,6 +108,24 @@ impl OverrideBlock {
                 pub find_or
                 // pub const fn find_or_none(&str) -> Self
                 pub find_or_none
+                // pub const fn row_of(flag: Self) -> Option<&'static FlagRow<Self>>
+                pub row_of
+                // pub const fn parent_of(flag: Self) -> Option<Self>
+                pub parent_of
+                // pub const fn parent_of_by_name(name: &str) -> Option<Self>
+                pub parent_of_by_name
+                // pub const fn children_of(group: Self) -> &'static [Self]
+                pub children_of
+                // pub const fn children_of_by_name(name: &str) -> &'static [Self]
+                pub children_of_by_name
+                // pub const fn ancestors_of(flag: Self) -> &'static [Self]
+                pub ancestors_of
+                // pub const fn ancestors_of_by_name(name: &str) -> &'static [Self]
+                pub ancestors_of_by_name
+                // pub const fn depth_of(flag: Self) -> Option<usize>
+                pub depth_of
+                // pub const fn depth_of_by_name(name: &str) -> Option<usize>
+                pub depth_of_by_name
                 // pub const fn count_ones(self) -> u32
                 pub count_ones
                 // pub const fn count_zeros(self) -> u32
@@ -174,6 +192,15 @@ impl OverrideBlock {
                 pub as_mut_slice
                 // pub const fn into_inner(self) -> [MaskTy; MaskCount]
                 pub into_inner
+                // Only generated for integer storage: `struct Perms(u32);`
+                // pub const fn bits(self) -> MaskTy
+                pub bits
+                // pub const fn from_bits(bits: MaskTy) -> Option<Self>
+                pub from_bits
+                // pub const fn from_bits_truncate(bits: MaskTy) -> Self
+                pub from_bits_truncate
+                // pub const fn from_bits_retain(bits: MaskTy) -> Self
+                pub from_bits_retain
                 // pub const fn as_bytes(&self) -> &[u8]
                 pub as_bytes
                 // pub const fn as_mut_bytes(&mut self) -> &mut [u8]
@@ -242,6 +269,28 @@ impl OverrideBlock {
                 pub len
                 // pub const fn is_valid(self) -> bool
                 pub is_valid
+                // pub fn iter(self) -> Iter<Self>
+                pub iter
+                // pub fn iter_indices(self) -> IterIndices<Self>
+                pub iter_indices
+                // pub fn iter_names(self) -> IterNames<Self>
+                pub iter_names
+                // pub fn iter_groups(self) -> IterGroups<Self>
+                pub iter_groups
+                // pub fn decompose_named(self) -> DecomposeNamed<Self>
+                pub decompose_named
+                // Only generated when the const block declares exclusive sets.
+                // pub const fn validate(self) -> Result<(), Conflict<Self>>
+                pub validate
+                // pub const fn set_exclusive(&mut self, flag: Self) -> &mut Self
+                pub set_exclusive
+                // Only generated when the const block declares implications.
+                // pub const fn normalize(self) -> Self
+                pub normalize
+                // pub const fn is_normalized(self) -> bool
+                pub is_normalized
+                // pub const fn with_implied(self, flag: Self) -> Self
+                pub with_implied
             ]
         }
     }
@@ -311,8 +360,7 @@ impl Parse for OverrideBlock {
 pub enum OverrideStage {
     Functions,
     Operators,
-    // TODO: Enable this when you need it.
-    // FlagsTrait,
+    FlagsTrait,
 }
 
 pub struct Overrider<'a> {
@@ -365,7 +413,7 @@ impl<'a> VisitMut for Overrider<'a> {
     
     fn visit_expr_method_call_mut(&mut self, i: &mut syn::ExprMethodCall) {
         if let syn::Expr::Path(exp) = &mut *i.receiver {
-            if exp.path.segments.len() < 1 {
+            if exp.path.segments.is_empty() {
                 syn::visit_mut::visit_expr_method_call_mut(self, i);
                 return;
             }
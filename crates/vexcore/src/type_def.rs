@@ -1,16 +1,26 @@
-use syn::{Attribute, Ident, Token, Type, Visibility, bracketed, parenthesized, parse::Parse, token::Paren};
+use syn::{Attribute, Ident, Token, Type, Visibility, bracketed, parenthesized, parse::Parse, token::{Bracket, Paren}};
 use vexmacro::*;
 
 fn default_mask_type() -> Type {
     syn::parse_quote!(u32)
 }
 
+/// How the bits of the flags type are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Storage {
+    /// `struct Perms([u32]);` stores `masks: [u32; MASK_COUNT]`.
+    Array,
+    /// `struct Perms(u32);` stores `bits: u32`. Every flag must fit in the one integer.
+    Integer,
+}
+
 pub struct TypeDef {
     pub attrs: Vec<Attribute>,
     pub vis: Visibility,
     pub type_name: Ident,
     pub mask_vis: Visibility,
     pub mask_type: Type,
+    pub storage: Storage,
 }
 
 impl Parse for TypeDef {
@@ -20,28 +30,31 @@ impl Parse for TypeDef {
         let vis = input.parse()?;
         _ = input.parse::<Token![struct]>()?;
         let type_name = input.parse()?;
-        let (mask_vis, mask_type) = if input.peek(Paren) {
+        let (mask_vis, mask_type, storage) = if input.peek(Paren) {
             let inner;
             parenthesized!(inner in input);
             let semi_result = input.parse::<Token![;]>();
             let mask_vis = inner.parse()?;
-            (
-                mask_vis,
-                {
-                    let sliced;
-                    bracketed!(sliced in inner);
-                    let mask_type = sliced.parse()?;
-                    let first_result = ensure_eof!(sliced);
-                    let second_result = ensure_eof!(inner);
-                    combine_results!(first_result, second_result, semi_result)?;
-                    mask_type
-                }
-            )
+            if inner.peek(Bracket) {
+                let sliced;
+                bracketed!(sliced in inner);
+                let mask_type = sliced.parse()?;
+                let first_result = ensure_eof!(sliced);
+                let second_result = ensure_eof!(inner);
+                combine_results!(first_result, second_result, semi_result)?;
+                (mask_vis, mask_type, Storage::Array)
+            } else {
+                let mask_type = inner.parse()?;
+                let eof_result = ensure_eof!(inner);
+                combine_results!(eof_result, semi_result)?;
+                (mask_vis, mask_type, Storage::Integer)
+            }
         } else {
             input.parse::<Token![;]>()?;
             (
                 Visibility::Inherited,
                 default_mask_type(),
+                Storage::Array,
             )
         };
         Ok(Self {
@@ -50,6 +63,7 @@ impl Parse for TypeDef {
             type_name,
             mask_vis,
             mask_type,
+            storage,
         })
     }
 }
\ No newline at end of file
@@ -30,7 +30,7 @@ macro_rules! combine_results {
     };
 }
 
-/// ```rust,no_run
+/// ```rust,ignore
 /// const fn comparer(lhs: u32, rhs: u32) -> ::core::cmp::Ordering;
 /// const_binary_search_fn!(
 ///     use path::to::comparer;
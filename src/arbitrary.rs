@@ -0,0 +1,52 @@
+//! [arbitrary] support for flags types, enabled with the `arbitrary` feature.
+//!
+//! Every flags type generated by [flags!](crate::flags) implements
+//! [arbitrary::Arbitrary] with [any_valid], so fuzz targets can take flags as input.
+//!
+//! The values only set the bits of single flags, so they never have unused bits set
+//! (see `is_valid`). Each byte of input decides eight flags, in bit index order.
+
+use ::arbitrary::{Result, Unstructured};
+
+use crate::Flags;
+
+/// Values with `required` and any of the single flags in `allowed`.
+fn between<T: Flags>(u: &mut Unstructured<'_>, required: T, allowed: T) -> Result<T> {
+    let mut flags = required;
+    let mut byte = 0u8;
+    for (i, row) in T::SINGLE_FLAGS.iter().enumerate() {
+        if i % 8 == 0 {
+            byte = u.arbitrary()?;
+        }
+        if byte & (1 << (i % 8)) != 0 && allowed.has_all(row.value) {
+            Flags::add(&mut flags, row.value);
+        }
+    }
+    Ok(flags)
+}
+
+/// Any valid value of `T`.
+pub fn any_valid<T: Flags>(u: &mut Unstructured<'_>) -> Result<T> {
+    between(u, T::NONE, T::ALL)
+}
+
+/// A value that only has flags of `mask`.
+///
+/// Bits of `mask` that are not part of a single flag are never set.
+pub fn subset_of<T: Flags>(u: &mut Unstructured<'_>, mask: T) -> Result<T> {
+    between(u, T::NONE, mask)
+}
+
+/// A value that has all of the bits of `mask`, and any other flags.
+///
+/// The value is only valid if `mask` is valid.
+pub fn superset_of<T: Flags>(u: &mut Unstructured<'_>, mask: T) -> Result<T> {
+    between(u, mask, T::ALL)
+}
+
+/// The number of bytes that [any_valid] takes from the input.
+#[must_use]
+pub const fn size_hint<T: Flags>() -> (usize, Option<usize>) {
+    let bytes = T::SINGLE_FLAG_COUNT.div_ceil(8);
+    (bytes, Some(bytes))
+}
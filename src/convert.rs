@@ -0,0 +1,83 @@
+use crate::{Flags, IterNames};
+
+/// The result of converting a flags value to another flags type by name.
+///
+/// See [Flags::convert_by_name] and [convert_by_name!](crate::convert_by_name).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConvertedFlags<T, U> {
+    /// The single flags of `U` with the same names as the single flags of the original value.
+    pub value: U,
+    /// The single flags of the original value that have no counterpart in `U`, along with
+    /// the bits of the original value that are not part of a single flag.
+    pub unmatched: T,
+}
+
+impl<T: Flags, U: Flags> ConvertedFlags<T, U> {
+    /// The names of the single flags that have no counterpart.
+    #[inline]
+    pub fn unmatched_names(&self) -> IterNames<T> {
+        self.unmatched.iter_names()
+    }
+
+    /// Test if every bit of the original value was converted.
+    #[inline]
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.unmatched == T::NONE
+    }
+}
+
+/// Convert a flags value to another flags type by name at compile time.
+///
+/// This is the const-evaluable version of [Flags::convert_by_name]. Both types must be
+/// generated by [flags!](crate::flags).
+///
+/// ```
+/// use vexillo::{ConvertedFlags, convert_by_name};
+/// vexillo::flags! {
+///     pub struct Perms(pub [u8]);
+///     pub const {
+///         SEND_MESSAGES
+///         BAN_USER
+///         MANAGE_SERVER
+///     }
+/// }
+/// vexillo::flags! {
+///     pub struct ChannelPerms(pub [u8]);
+///     pub const {
+///         BAN_USER
+///         SEND_MESSAGES
+///     }
+/// }
+/// const CHANNEL: ConvertedFlags<Perms, ChannelPerms> = convert_by_name!(Perms::ALL, Perms => ChannelPerms);
+/// assert_eq!(CHANNEL.value, ChannelPerms::ALL);
+/// assert_eq!(CHANNEL.unmatched, Perms::MANAGE_SERVER);
+/// ```
+#[macro_export]
+macro_rules! convert_by_name {
+    ($value:expr, $from:ty => $to:ty $(,)?) => {{
+        const fn convert(value: $from) -> $crate::ConvertedFlags<$from, $to> {
+            let mut converted = <$to as $crate::Flags>::NONE;
+            let mut unmatched = value;
+            let singles = <$from as $crate::Flags>::SINGLE_FLAGS;
+            let targets = $crate::FlagRows::new(<$to as $crate::Flags>::FLAGS_TABLE);
+            let mut i = 0;
+            while i < singles.len() {
+                let index = singles[i].bit_index();
+                if value.__vexillo_get_bit(index) {
+                    let target = targets.find(singles[i].name, <$to as $crate::Flags>::ORDERED_SINGLE_FLAG_INDICES);
+                    if let ::core::option::Option::Some(target) = target {
+                        converted.__vexillo_set_bit(target.bit_index(), true);
+                        unmatched.__vexillo_set_bit(index, false);
+                    }
+                }
+                i += 1;
+            }
+            $crate::ConvertedFlags {
+                value: converted,
+                unmatched,
+            }
+        }
+        convert($value)
+    }};
+}
@@ -0,0 +1,98 @@
+//! Formatting helpers used by the generated `Debug` and `Display` impls.
+
+use core::fmt::{self, Formatter, Write};
+
+use crate::{Flags, internal::MustBeUnsignedInt};
+
+/// Which names are used when formatting a flags value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NameStyle {
+    /// Every single flag is named: `LOBBY | MESSAGE_MODS`.
+    Singles,
+    /// Groups that are fully covered are named instead of their flags: `GUEST`.
+    ///
+    /// See [Flags::decompose_named].
+    Groups,
+}
+
+/// Displays all of the bits of a flags value as one hexadecimal number, such as `0x800000`.
+pub(crate) struct HexBits<T: Flags>(pub T);
+
+impl<T: Flags> fmt::Display for HexBits<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let masks = self.0.as_slice();
+        let top = masks.iter()
+            .rposition(|&mask| mask != T::MaskType::ZERO)
+            .unwrap_or(0);
+        let width = (T::MASK_BITS / 4) as usize;
+        write!(f, "{:#x}", masks[top])?;
+        masks[..top].iter().rev().try_for_each(|mask| write!(f, "{mask:0width$x}"))
+    }
+}
+
+/// Parse hex digits (without the `0x` prefix) into the raw bits of a flags value.
+/// This reads what [HexBits] writes.
+pub(crate) fn parse_hex<T: Flags>(digits: &str) -> Option<T> {
+    if digits.is_empty() {
+        return None;
+    }
+    let mut flags = T::NONE;
+    for (nibble, digit) in digits.chars().rev().enumerate() {
+        let value = digit.to_digit(16)?;
+        for bit in (0..4).filter(|bit| value & (1 << bit) != 0) {
+            let index = u32::try_from(nibble * 4 + bit).ok().filter(|&index| index < T::BITS)?;
+            let mask = &mut flags.as_mut_slice()[(index / T::MASK_BITS) as usize];
+            *mask = mask.with_bit(index % T::MASK_BITS, true);
+        }
+    }
+    Some(flags)
+}
+
+/// Write the names of `flags` separated by `separator`, followed by the unknown bits.
+fn write_names<T: Flags>(flags: T, style: NameStyle, separator: &str, f: &mut Formatter<'_>) -> fmt::Result {
+    let mut first = true;
+    let mut write_name = |f: &mut Formatter<'_>, name: &str| {
+        if !first {
+            f.write_str(separator)?;
+        }
+        first = false;
+        f.write_str(name)
+    };
+    match style {
+        NameStyle::Singles => flags.iter_names().try_for_each(|name| write_name(f, name))?,
+        NameStyle::Groups => flags.decompose_named().try_for_each(|row| write_name(f, row.name))?,
+    }
+    let unknown = flags.without(T::ALL);
+    if unknown.count_ones() != 0 {
+        if !first {
+            f.write_str(separator)?;
+        }
+        write!(f, "{}", HexBits(unknown))?;
+    }
+    Ok(())
+}
+
+/// Format `flags` as its flag names joined with ` | `, such as `BAN_USER | UNBAN_USER`.
+///
+/// Bits that are not part of any flag are written as hex at the end, and an
+/// empty value is written as an empty string. The output is a flags expression,
+/// so `FromStr` parses it back to the same value with either [NameStyle].
+pub fn display<T: Flags>(flags: T, style: NameStyle, f: &mut Formatter<'_>) -> fmt::Result {
+    write_names(flags, style, " | ", f)
+}
+
+/// Format `flags` as `TypeName(BAN_USER | UNBAN_USER)`.
+///
+/// The alternate form (`{:#?}`) writes one flag per line.
+pub fn debug<T: Flags>(flags: T, type_name: &str, f: &mut Formatter<'_>) -> fmt::Result {
+    f.write_str(type_name)?;
+    f.write_char('(')?;
+    if f.alternate() && flags.count_ones() != 0 {
+        f.write_str("\n    ")?;
+        write_names(flags, NameStyle::Singles, " |\n    ", f)?;
+        f.write_char('\n')?;
+    } else {
+        write_names(flags, NameStyle::Singles, " | ", f)?;
+    }
+    f.write_char(')')
+}
@@ -1,20 +1,82 @@
 use ::core::cmp::Ordering;
 use ::core::ops::Range;
+use ::core::sync::atomic::Ordering as AtomicOrdering;
 pub use vexmacro::const_binary_search_fn;
 #[doc(hidden)]
 pub use vexproc::flags;
+#[cfg(feature = "serde")]
+pub use ::serde;
+#[cfg(feature = "proptest")]
+pub use ::proptest;
+#[cfg(feature = "arbitrary")]
+pub use ::arbitrary;
 
 mod private {
     pub trait Sealed {}
 }
 
-pub trait MustBeUnsignedInt: private::Sealed + Clone + Copy {}
+/// The unsigned integer types that can be used as a mask type.
+/// 
+/// The methods allow generic code (such as the iterators) to work with
+/// the masks of any flags type.
+pub trait MustBeUnsignedInt: private::Sealed + Clone + Copy + Eq + Send + Sync + ::core::fmt::LowerHex + SerdeMask + 'static {
+    const BITS: u32;
+    const ZERO: Self;
+    fn trailing_zeros(self) -> u32;
+    fn leading_zeros(self) -> u32;
+    fn count_ones(self) -> u32;
+    fn get_bit(self, bit: u32) -> bool;
+    fn with_bit(self, bit: u32, on: bool) -> Self;
+}
+
+/// Mask types can be serialized when the `serde` feature is enabled.
+#[cfg(feature = "serde")]
+pub trait SerdeMask: ::serde::Serialize + for<'de> ::serde::Deserialize<'de> {}
+#[cfg(feature = "serde")]
+impl<T: ::serde::Serialize + for<'de> ::serde::Deserialize<'de>> SerdeMask for T {}
+/// Mask types can be serialized when the `serde` feature is enabled.
+#[cfg(not(feature = "serde"))]
+pub trait SerdeMask {}
+#[cfg(not(feature = "serde"))]
+impl<T> SerdeMask for T {}
 
 macro_rules! mark_types {
     ($($type:ty),+$(,)?) => {
         $(
             impl private::Sealed for $type {}
-            impl MustBeUnsignedInt for $type {}
+            impl MustBeUnsignedInt for $type {
+                const BITS: u32 = <$type>::BITS;
+                const ZERO: Self = 0;
+                
+                #[inline(always)]
+                fn trailing_zeros(self) -> u32 {
+                    <$type>::trailing_zeros(self)
+                }
+                
+                #[inline(always)]
+                fn leading_zeros(self) -> u32 {
+                    <$type>::leading_zeros(self)
+                }
+                
+                #[inline(always)]
+                fn count_ones(self) -> u32 {
+                    <$type>::count_ones(self)
+                }
+                
+                #[inline(always)]
+                fn get_bit(self, bit: u32) -> bool {
+                    self & (1 << bit) != 0
+                }
+                
+                #[inline(always)]
+                fn with_bit(self, bit: u32, on: bool) -> Self {
+                    if on {
+                        self | (1 << bit)
+                    } else {
+                        self & !(1 << bit)
+                    }
+                }
+            }
         )*
     };
 }
@@ -31,6 +93,83 @@ mark_types!(
     // usize,
 );
 
+/// The mask types that have an atomic counterpart, used by the atomic companion types.
+/// 
+/// The atomic type has the same in-memory representation as the mask type.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` does not have an atomic type",
+    note = "`#[vexillo(atomic)]` requires a mask type of `u8`, `u16`, `u32`, or `u64`",
+)]
+pub trait AtomicMask: MustBeUnsignedInt {
+    type Atomic: Send + Sync;
+    fn load(atomic: &Self::Atomic, order: AtomicOrdering) -> Self;
+    fn store(atomic: &Self::Atomic, value: Self, order: AtomicOrdering);
+    fn fetch_or(atomic: &Self::Atomic, value: Self, order: AtomicOrdering) -> Self;
+    fn fetch_and(atomic: &Self::Atomic, value: Self, order: AtomicOrdering) -> Self;
+    fn fetch_xor(atomic: &Self::Atomic, value: Self, order: AtomicOrdering) -> Self;
+    fn compare_exchange(
+        atomic: &Self::Atomic,
+        current: Self,
+        new: Self,
+        success: AtomicOrdering,
+        failure: AtomicOrdering,
+    ) -> Result<Self, Self>;
+}
+
+macro_rules! atomic_masks {
+    ($($type:ty => $atomic:ty),+$(,)?) => {
+        $(
+            impl AtomicMask for $type {
+                type Atomic = $atomic;
+                
+                #[inline(always)]
+                fn load(atomic: &$atomic, order: AtomicOrdering) -> Self {
+                    atomic.load(order)
+                }
+                
+                #[inline(always)]
+                fn store(atomic: &$atomic, value: Self, order: AtomicOrdering) {
+                    atomic.store(value, order)
+                }
+                
+                #[inline(always)]
+                fn fetch_or(atomic: &$atomic, value: Self, order: AtomicOrdering) -> Self {
+                    atomic.fetch_or(value, order)
+                }
+                
+                #[inline(always)]
+                fn fetch_and(atomic: &$atomic, value: Self, order: AtomicOrdering) -> Self {
+                    atomic.fetch_and(value, order)
+                }
+                
+                #[inline(always)]
+                fn fetch_xor(atomic: &$atomic, value: Self, order: AtomicOrdering) -> Self {
+                    atomic.fetch_xor(value, order)
+                }
+                
+                #[inline(always)]
+                fn compare_exchange(
+                    atomic: &$atomic,
+                    current: Self,
+                    new: Self,
+                    success: AtomicOrdering,
+                    failure: AtomicOrdering,
+                ) -> Result<Self, Self> {
+                    atomic.compare_exchange(current, new, success, failure)
+                }
+            }
+        )*
+    };
+}
+
+atomic_masks!(
+    u8 => ::core::sync::atomic::AtomicU8,
+    u16 => ::core::sync::atomic::AtomicU16,
+    u32 => ::core::sync::atomic::AtomicU32,
+    u64 => ::core::sync::atomic::AtomicU64,
+    // There is no stable AtomicU128.
+);
+
 pub const fn mask_type_check<MaskType: MustBeUnsignedInt>() {}
 
 #[doc(hidden)]
@@ -83,7 +222,6 @@ macro_rules! counter_impls {
                     next
                 }
                 
-                #[must_use]
                 #[inline(always)]
                 pub const fn incr(&mut self) {
                     _=self.next();
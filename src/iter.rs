@@ -0,0 +1,373 @@
+use ::core::iter::FusedIterator;
+use ::core::ops::Range;
+
+use crate::internal::MustBeUnsignedInt;
+use crate::{Flags, FlagRow, FlagRows};
+
+/// An iterator over the indices of the bits that are set in a flags value.
+/// 
+/// Bits are found by scanning each mask with `trailing_zeros` (or `leading_zeros`
+/// from the back), so empty masks are skipped in a single step.
+#[derive(Debug, Clone)]
+pub struct IterIndices<T: Flags> {
+    remaining: T,
+    front_mask: usize,
+    back_mask: usize,
+    len: usize,
+}
+
+impl<T: Flags> IterIndices<T> {
+    /// Create an iterator over the set bits of `flags`. Unused bits are ignored.
+    #[must_use]
+    #[inline]
+    pub fn new(flags: T) -> Self {
+        let remaining = flags.and(T::ALL);
+        Self {
+            remaining,
+            front_mask: 0,
+            back_mask: T::MASK_COUNT,
+            len: remaining.count_ones() as usize,
+        }
+    }
+}
+
+impl<T: Flags> Iterator for IterIndices<T> {
+    type Item = u32;
+    
+    fn next(&mut self) -> Option<u32> {
+        if self.len == 0 {
+            return None;
+        }
+        let masks = self.remaining.as_mut_slice();
+        // len > 0 guarantees that there is a set bit in front_mask..back_mask.
+        loop {
+            let mask = masks[self.front_mask];
+            if mask != T::MaskType::ZERO {
+                let bit = mask.trailing_zeros();
+                masks[self.front_mask] = mask.with_bit(bit, false);
+                self.len -= 1;
+                return Some(self.front_mask as u32 * T::MASK_BITS + bit);
+            }
+            self.front_mask += 1;
+        }
+    }
+    
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T: Flags> DoubleEndedIterator for IterIndices<T> {
+    fn next_back(&mut self) -> Option<u32> {
+        if self.len == 0 {
+            return None;
+        }
+        let masks = self.remaining.as_mut_slice();
+        loop {
+            let mask = masks[self.back_mask - 1];
+            if mask != T::MaskType::ZERO {
+                let bit = T::MASK_BITS - 1 - mask.leading_zeros();
+                masks[self.back_mask - 1] = mask.with_bit(bit, false);
+                self.len -= 1;
+                return Some((self.back_mask - 1) as u32 * T::MASK_BITS + bit);
+            }
+            self.back_mask -= 1;
+        }
+    }
+}
+
+impl<T: Flags> ExactSizeIterator for IterIndices<T> {}
+impl<T: Flags> FusedIterator for IterIndices<T> {}
+
+/// An iterator over the single flags that are set in a flags value.
+#[derive(Debug, Clone)]
+pub struct Iter<T: Flags> {
+    indices: IterIndices<T>,
+}
+
+impl<T: Flags> Iter<T> {
+    #[must_use]
+    #[inline]
+    pub fn new(flags: T) -> Self {
+        Self {
+            indices: IterIndices::new(flags),
+        }
+    }
+}
+
+impl<T: Flags> Iterator for Iter<T> {
+    type Item = T;
+    
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.indices.next().map(T::from_index)
+    }
+    
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl<T: Flags> DoubleEndedIterator for Iter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.indices.next_back().map(T::from_index)
+    }
+}
+
+impl<T: Flags> ExactSizeIterator for Iter<T> {}
+impl<T: Flags> FusedIterator for Iter<T> {}
+
+/// An iterator over the names of the single flags that are set in a flags value.
+#[derive(Debug, Clone)]
+pub struct IterNames<T: Flags> {
+    indices: IterIndices<T>,
+}
+
+impl<T: Flags> IterNames<T> {
+    #[must_use]
+    #[inline]
+    pub fn new(flags: T) -> Self {
+        Self {
+            indices: IterIndices::new(flags),
+        }
+    }
+    
+    /// Skip the flags that are marked `#[deprecated]`.
+    #[must_use]
+    pub fn skip_deprecated(self) -> Self {
+        let deprecated = T::SINGLE_FLAGS.iter()
+            .filter(|row| row.is_deprecated())
+            .fold(T::NONE, |mask, row| mask.with(row.value));
+        // `remaining` only has the bits that were not yielded yet.
+        Self {
+            indices: IterIndices::new(self.indices.remaining.without(deprecated)),
+        }
+    }
+    
+    #[inline]
+    fn name(index: u32) -> &'static str {
+        // `IterIndices` only yields indices of bits in `ALL`, which all have a row.
+        match FlagRows::new(T::SINGLE_FLAGS).find_index(index) {
+            Some(row) => row.name,
+            None => unreachable!(),
+        }
+    }
+}
+
+impl<T: Flags> Iterator for IterNames<T> {
+    type Item = &'static str;
+    
+    #[inline]
+    fn next(&mut self) -> Option<&'static str> {
+        self.indices.next().map(Self::name)
+    }
+    
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl<T: Flags> DoubleEndedIterator for IterNames<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'static str> {
+        self.indices.next_back().map(Self::name)
+    }
+}
+
+impl<T: Flags> ExactSizeIterator for IterNames<T> {}
+impl<T: Flags> FusedIterator for IterNames<T> {}
+
+/// An iterator over the group flags that are fully contained in a flags value.
+/// 
+/// Empty groups are never yielded.
+#[derive(Debug, Clone)]
+pub struct IterGroups<T: Flags> {
+    flags: T,
+    groups: Range<usize>,
+}
+
+impl<T: Flags> IterGroups<T> {
+    #[must_use]
+    #[inline]
+    pub fn new(flags: T) -> Self {
+        Self {
+            flags,
+            groups: 0..T::GROUP_FLAG_COUNT,
+        }
+    }
+    
+    #[inline]
+    fn contains(&self, index: usize) -> Option<&'static FlagRow<T>> {
+        let row = &T::GROUP_FLAGS[index];
+        (row.bits() != 0 && self.flags.has_all(row.value)).then_some(row)
+    }
+}
+
+impl<T: Flags> Iterator for IterGroups<T> {
+    type Item = &'static FlagRow<T>;
+    
+    fn next(&mut self) -> Option<&'static FlagRow<T>> {
+        while let Some(index) = self.groups.next() {
+            if let Some(row) = self.contains(index) {
+                return Some(row);
+            }
+        }
+        None
+    }
+    
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.groups.len()))
+    }
+}
+
+impl<T: Flags> DoubleEndedIterator for IterGroups<T> {
+    fn next_back(&mut self) -> Option<&'static FlagRow<T>> {
+        while let Some(index) = self.groups.next_back() {
+            if let Some(row) = self.contains(index) {
+                return Some(row);
+            }
+        }
+        None
+    }
+}
+
+impl<T: Flags> FusedIterator for IterGroups<T> {}
+
+/// An iterator over the fewest named flags that make up a flags value.
+/// 
+/// The groups are the smallest set of groups that, together with a single flag for
+/// each bit that no chosen group covers, make up the value. For nested groups this is
+/// e.g. `ADMIN | CLEAR_LOG` rather than every single flag of `ADMIN`. Groups may
+/// overlap when that takes fewer names, single flags never do, and unused bits are ignored.
+/// 
+/// The set is found by an exact search when the iterator is created. Only groups that are
+/// not contained by another group that fits are searched, so nested groups are cheap, but
+/// many overlapping groups make the search slower. Among sets of the same size, groups are
+/// then narrowed down to overlap as little as possible. Rows are yielded largest first.
+#[derive(Debug, Clone)]
+pub struct DecomposeNamed<T: Flags> {
+    rows: ::std::vec::IntoIter<&'static FlagRow<T>>,
+}
+
+impl<T: Flags> DecomposeNamed<T> {
+    #[must_use]
+    pub fn new(flags: T) -> Self {
+        let flags = flags.and(T::ALL);
+        let rows = FlagRows::new(T::FLAGS_TABLE);
+        let bit_ordered = T::BIT_ORDERED_FLAGS_TABLE;
+        // Rows larger than `flags` can not fit, so skip past them. Groups with a single bit
+        // are never better than the single flag.
+        let start = rows.upper_bit_count_search(flags.count_ones(), bit_ordered);
+        let mut fitting = Vec::<&'static FlagRow<T>>::new();
+        for index in &bit_ordered[start..] {
+            let row = rows.row(index.index());
+            if row.is_group()
+                && row.bits() > 1
+                && flags.has_all(row.value)
+                && !fitting.iter().any(|group| group.value == row.value)
+            {
+                fitting.push(row);
+            }
+        }
+        // A group that is contained by another group that fits can always be replaced by it,
+        // so only the largest groups are searched.
+        let groups = fitting.iter()
+            .copied()
+            .filter(|row| !fitting.iter().any(|group| group.value != row.value && group.value.has_all(row.value)))
+            .collect::<Vec<_>>();
+        let mut search = CoverSearch {
+            groups: &groups,
+            chosen: Vec::new(),
+            best: Vec::new(),
+            best_len: usize::MAX,
+        };
+        search.cover(flags, 0);
+        let mut chosen = search.best.iter().map(|&group| groups[group]).collect::<Vec<_>>();
+        // Then each group is swapped for the smallest group that still covers the bits that no
+        // other chosen group covers, so that groups only overlap where it saves a name.
+        for i in 0..chosen.len() {
+            let others = chosen.iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(T::NONE, |others, (_, group)| others.or(group.value));
+            let needed = chosen[i].value.without(others);
+            if let Some(&smaller) = fitting.iter().rev().find(|group| group.value.has_all(needed)) {
+                chosen[i] = smaller;
+            }
+        }
+        let covered = chosen.iter().fold(T::NONE, |covered, group| covered.or(group.value));
+        let named = bit_ordered.iter()
+            .map(|index| rows.row(index.index()))
+            .filter(|&row| if row.is_group() {
+                chosen.iter().any(|&group| ::core::ptr::eq(group, row))
+            } else {
+                flags.without(covered).has_all(row.value)
+            })
+            .collect::<Vec<_>>();
+        Self {
+            rows: named.into_iter(),
+        }
+    }
+}
+
+/// A depth-first search for the fewest groups and single flags that make up a value.
+struct CoverSearch<'a, T: Flags> {
+    /// The groups that may be chosen, largest first.
+    groups: &'a [&'static FlagRow<T>],
+    chosen: Vec<usize>,
+    /// The chosen groups of the best cover found so far.
+    best: Vec<usize>,
+    /// The number of names in `best`, including the single flags.
+    best_len: usize,
+}
+
+impl<T: Flags> CoverSearch<'_, T> {
+    /// Cover `remaining`, where `len` names have been chosen already.
+    fn cover(&mut self, remaining: T, len: usize) {
+        let Some(bit) = remaining.iter_indices().next() else {
+            if len < self.best_len {
+                self.best_len = len;
+                self.best.clone_from(&self.chosen);
+            }
+            return;
+        };
+        // Every branch takes at least one more name.
+        if len + 1 >= self.best_len {
+            return;
+        }
+        // The lowest bit is covered by one of the groups that contain it, or by its single flag.
+        // Larger groups are tried first, so the first cover found is usually close to the best.
+        for group in 0..self.groups.len() {
+            let row = self.groups[group];
+            if row.value.get(bit) {
+                self.chosen.push(group);
+                self.cover(remaining.without(row.value), len + 1);
+                self.chosen.pop();
+            }
+        }
+        self.cover(remaining.without(T::from_index(bit)), len + 1);
+    }
+}
+
+impl<T: Flags> Iterator for DecomposeNamed<T> {
+    type Item = &'static FlagRow<T>;
+    
+    #[inline]
+    fn next(&mut self) -> Option<&'static FlagRow<T>> {
+        self.rows.next()
+    }
+    
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
+
+impl<T: Flags> ExactSizeIterator for DecomposeNamed<T> {}
+
+impl<T: Flags> FusedIterator for DecomposeNamed<T> {}
@@ -9,12 +9,15 @@
 //!     // Although it is defined as a tuple struct, the resulting
 //!     // struct looks like this:
 //!     // #[repr(transparent)]
-//!     // #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+//!     // #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 //!     // pub struct MyFlags {
 //!     //     pub masks: [u16; _], // where _ is the mask count.
 //!     // }
 //!     // Minimal requirement:
 //!     // `struct MyFlags;`, becomes `struct MyFlags([u32]);`
+//!     // Without the brackets, `pub struct MyFlags(pub u16);` stores a single
+//!     // integer instead, `pub bits: u16`, with straight-line bit ops and
+//!     // `bits`/`from_bits` accessors. Every flag must fit in that integer.
 //!     // Declare flag constants.
 //!     // Specify visibility of constants (use priv for private).
 //!     pub const {
@@ -78,21 +81,60 @@
 //! ````````
 //! ___
 
+mod convert;
+pub mod fmt;
 #[doc(hidden)]
 pub mod internal;
+mod iter;
+mod map;
+mod parse;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
 mod shared;
+pub use convert::*;
+pub use iter::*;
+pub use map::*;
+pub use parse::*;
 pub use shared::*;
 
 /// Create a bitflags type.
 /// 
 /// # Example
 /// ```
-/// flags! {
+/// vexillo::flags! {
 ///     // Define type with `vis struct Name(vis [FlagIntType]);
 ///     // FlagIntType must be one of the following: u8, u16, u32, or u64.
 ///     // The FlagIntType determines the type to use for bit masks. `vis` determines
 ///     // visibility. Default visibility is private.
+///     // Use `vis struct Name(vis FlagIntType);` to store the flags in a single integer.
 ///     /// Example flags struct.
+///     // Optional: options for the generated type.
+///     // `debug = names` (default) formats as `ExampleFlags(FLAG0 | ALPHA)`,
+///     // `debug = raw` derives `Debug` instead.
+///     // `display = names` (default) formats as `FLAG0 | ALPHA`,
+///     // `display = groups` names the groups that are fully covered,
+///     // `display = none` does not implement `Display`.
+///     // With the `serde` feature, `serde(repr = auto|names|bytes|masks, unknown = error|ignore|preserve)`
+///     // selects how `Serialize`/`Deserialize` represent the flags. See the `serde` module.
+///     // `atomic` also generates `AtomicExampleFlags` for sharing the flags between threads
+///     // (`atomic = Name` picks the name). The mask type must be `u8`, `u16`, `u32`, or `u64`.
+///     // `flag_enum` also generates `enum ExampleFlagsFlag { Flag0, Flag1, ... }` with a variant
+///     // for each single flag (`flag_enum = Name` picks the name). Variants convert to and from
+///     // the flags type with `From`/`TryFrom`, and `|` between variants creates the flags type.
+///     // `c_header` makes the struct `#[repr(C)]` and adds `ExampleFlags::C_HEADER`, a C header
+///     // with an `example_flags_t` struct, a `#define` for each mask word of every flag, and
+///     // helper functions (`c_header = name` picks the C name). The mask type must be written
+///     // as `u8`, `u16`, `u32`, or `u64`.
+///     // `lock = "flags.lock"` records the bit index of every single flag in a lockfile
+///     // (relative to the crate directory). Recorded flags keep their index, new flags are
+///     // appended, and adding a flag that is not recorded or removing or moving a recorded flag
+///     // is an error. The lockfile is only written while the `VEXILLO_UPDATE_LOCK` environment
+///     // variable is set, which records new flags and allows removing and moving flags.
+///     #[vexillo(debug = names, display = groups, atomic, flag_enum)]
 ///     pub struct ExampleFlags(pub [u64]);
 ///     // Optional:
 ///     override {
@@ -113,6 +155,15 @@ pub use shared::*;
 ///         pub find
 ///         pub find_or
 ///         pub find_or_none
+///         pub row_of
+///         pub parent_of
+///         pub parent_of_by_name
+///         pub children_of
+///         pub children_of_by_name
+///         pub ancestors_of
+///         pub ancestors_of_by_name
+///         pub depth_of
+///         pub depth_of_by_name
 ///         pub count_ones
 ///         pub count_zeros
 ///         pub add
@@ -129,8 +180,8 @@ pub use shared::*;
 ///         pub has_all
 ///         pub has_none
 ///         pub has_any
-///         pub masks
-///         pub masks_mut
+///         pub as_slice
+///         pub as_mut_slice
 ///         pub into_inner
 ///         pub as_bytes
 ///         pub as_mut_bytes
@@ -153,9 +204,24 @@ pub use shared::*;
 ///         // Comparisons
 ///         pub eq
 ///         pub ne
+///         // Only generated when the const block declares exclusive sets
+///         pub validate
+///         pub set_exclusive
+///         // Only generated when the const block declares implications
+///         pub normalize
+///         pub is_normalized
+///         pub with_implied
 ///     }
 ///     pub const {
 ///         FLAG0
+///         // Pin a flag to a bit index with `= index`. Pinned indices must be unique.
+///         // Unpinned flags are assigned the lowest free index, in declaration order.
+///         PINNED = 63
+///         // Reserve bits without declaring a flag. Reserved bits are never part of `ALL`.
+///         // `reserved N` reserves the next N free indices, `_` reserves the next free index,
+///         // and `_ = index` reserves a specific index.
+///         reserved 2
+///         _ = 62
 ///         // Declaration
 ///         priv DECLARATION
 ///         // Group
@@ -167,6 +233,11 @@ pub use shared::*;
 ///             CAPPA
 ///         ]
 ///         // Empty group to specify no flags set.
+///         // The macro warns about suspicious groups: `+`/`-` updates that change nothing
+///         // (`vexillo::redundant`), groups with the same flags as an earlier group
+///         // (`vexillo::duplicate`), and groups without flags (`vexillo::empty`).
+///         // Allow a lint on a group, and the groups declared in it, to silence it.
+///         #[allow(vexillo::empty)]
 ///         pub TEST: []
 ///         pub FLAGS: [
 ///             // [vis] <identifier> is a flag declaration. These flags will be assigned a single bit, in the order that they appear.
@@ -194,8 +265,36 @@ pub use shared::*;
 ///                 THREE
 ///             ]
 ///         ]
+///         // The nesting is kept: `ExampleFlags::parent_of(ExampleFlags::ONE)` is `Some(PUBLIC)`,
+///         // `children_of(PUBLIC)` is `[ONE, TWO, THREE]`, `ancestors_of(ONE)` is `[PUBLIC, FLAGS]`,
+///         // and `depth_of(ONE)` is `Some(2)`. The `*_by_name` versions tell apart flags with the same value.
+///         // Groups can reference flags that are declared anywhere in the block,
+///         // as long as the references are not cyclic.
+///         pub LATER: [+LAST]
 ///         // You can bind a flag to another name with this simple trick:
 ///         pub FULL: [+ALL]
+///         LAST
+///         // Keep a renamed flag compiling with `alias OLD_NAME = NEW_NAME`, which declares a
+///         // `#[deprecated]` constant with the value of `NEW_NAME`. `try_find` and `parse` accept
+///         // the alias, but it is not part of the flag table, so it is never counted or formatted.
+///         // Aliases are declared at the top level and can't be used in `+`/`-` updates.
+///         alias BANANAS = BANANA
+///         // `#[deprecated]` flags are marked in the flag table (`FlagRow::is_deprecated`),
+///         // and `iter_names().skip_deprecated()` leaves them out.
+///         #[deprecated]
+///         OLD_FLAG
+///         // At most one flag of an exclusive set may be set. The sets are listed in
+///         // `EXCLUSIVE_SETS`, `validate()` reports a value with two flags of a set, and
+///         // `set_exclusive(flag)` adds `flag` and removes the rest of its sets.
+///         // Groups with two flags of an exclusive set are an error, so this example can't
+///         // have one because `FULL` has every flag:
+///         // exclusive [APPLE, BANANA, STRAWBERRY]
+///         // `implies A => B | C` adds `B` and `C` to values with `A` when they are normalized.
+///         // Implications are transitive. `IMPLICATIONS` lists every implied flag of each flag,
+///         // `normalize()` adds the implied flags, `is_normalized()` tests for them, and
+///         // `with_implied(flag)` joins with `flag` and its implied flags. A flag that implies
+///         // two flags of an exclusive set (or one besides itself) is an error.
+///         implies STRAWBERRY => APPLE
 ///     }
 /// }
 /// ```
@@ -0,0 +1,247 @@
+use ::core::fmt;
+use ::core::iter::FusedIterator;
+
+use crate::Flags;
+
+/// A value for each single flag of `T`, stored inline in `[Option<V>; T::SINGLE_FLAG_COUNT]`.
+///
+/// Keys are single flags. Passing a group, a value with more than one bit set, or
+/// a bit that is not a single flag (such as a reserved bit) panics.
+///
+/// Iteration is in bit index order.
+/// ```
+/// vexillo::flags! {
+///     pub struct Perms(pub u8);
+///     pub const {
+///         READ
+///         WRITE
+///     }
+/// }
+/// let mut costs = vexillo::FlagMap::<Perms, u32>::new();
+/// costs.insert(Perms::WRITE, 10);
+/// costs.insert(Perms::READ, 1);
+/// assert_eq!(costs.get(Perms::WRITE), Some(&10));
+/// assert_eq!(costs.keys(), Perms::READ | Perms::WRITE);
+/// assert_eq!(costs.values().sum::<u32>(), 11);
+/// ```
+pub struct FlagMap<T: Flags, V> {
+    values: T::MapArrayType<V>,
+}
+
+/// The position of the single flag `flag` in [Flags::SINGLE_FLAGS].
+#[track_caller]
+fn slot<T: Flags>(flag: T) -> usize {
+    // `iter_indices` skips unknown bits, so the row's value is compared in full below.
+    let Some(index) = flag.iter_indices().next() else {
+        panic!("FlagMap keys must be single flags.");
+    };
+    match T::SINGLE_FLAGS.binary_search_by(|row| row.index().cmp(&Some(index))) {
+        Ok(slot) if T::SINGLE_FLAGS[slot].value.eq(flag) => slot,
+        _ => panic!("FlagMap keys must be single flags."),
+    }
+}
+
+impl<T: Flags, V> FlagMap<T, V> {
+    /// Create an empty map.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            values: T::map_array_from_fn(|_| None),
+        }
+    }
+    
+    /// Set the value of `flag`, returning the previous value.
+    ///
+    /// Panics if `flag` is not a single flag.
+    #[track_caller]
+    #[inline]
+    pub fn insert(&mut self, flag: T, value: V) -> Option<V> {
+        self.values.as_mut()[slot(flag)].replace(value)
+    }
+    
+    /// Remove the value of `flag`, returning it.
+    ///
+    /// Panics if `flag` is not a single flag.
+    #[track_caller]
+    #[inline]
+    pub fn remove(&mut self, flag: T) -> Option<V> {
+        self.values.as_mut()[slot(flag)].take()
+    }
+    
+    /// Panics if `flag` is not a single flag.
+    #[must_use]
+    #[track_caller]
+    #[inline]
+    pub fn get(&self, flag: T) -> Option<&V> {
+        self.values.as_ref()[slot(flag)].as_ref()
+    }
+    
+    /// Panics if `flag` is not a single flag.
+    #[must_use]
+    #[track_caller]
+    #[inline]
+    pub fn get_mut(&mut self, flag: T) -> Option<&mut V> {
+        self.values.as_mut()[slot(flag)].as_mut()
+    }
+    
+    /// Panics if `flag` is not a single flag.
+    #[must_use]
+    #[track_caller]
+    #[inline]
+    pub fn contains_key(&self, flag: T) -> bool {
+        self.values.as_ref()[slot(flag)].is_some()
+    }
+    
+    /// The union of the flags that have a value.
+    #[must_use]
+    pub fn keys(&self) -> T {
+        self.iter().fold(T::NONE, |keys, (flag, _)| keys.with(flag))
+    }
+    
+    /// The number of flags that have a value.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.as_ref().iter().filter(|value| value.is_some()).count()
+    }
+    
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.as_ref().iter().all(Option::is_none)
+    }
+    
+    /// Remove all values.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.values.as_mut().iter_mut().for_each(|value| *value = None);
+    }
+    
+    /// Iterate over the flags and their values in bit index order.
+    #[inline]
+    pub fn iter(&self) -> FlagMapIter<'_, T, V> {
+        FlagMapIter {
+            rows: T::SINGLE_FLAGS.iter().zip(self.values.as_ref().iter()),
+        }
+    }
+    
+    /// Iterate over the flags and mutable references to their values in bit index order.
+    #[inline]
+    pub fn iter_mut(&mut self) -> FlagMapIterMut<'_, T, V> {
+        FlagMapIterMut {
+            rows: T::SINGLE_FLAGS.iter().zip(self.values.as_mut().iter_mut()),
+        }
+    }
+    
+    /// Iterate over the values in bit index order.
+    #[inline]
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.values.as_ref().iter().flatten()
+    }
+}
+
+impl<T: Flags, V> Default for FlagMap<T, V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Flags, V: Clone> Clone for FlagMap<T, V> {
+    fn clone(&self) -> Self {
+        let values = self.values.as_ref();
+        Self {
+            values: T::map_array_from_fn(|i| values[i].clone()),
+        }
+    }
+}
+
+impl<T: Flags, V: PartialEq> PartialEq for FlagMap<T, V> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.values.as_ref() == other.values.as_ref()
+    }
+}
+
+impl<T: Flags, V: Eq> Eq for FlagMap<T, V> {}
+
+impl<T: Flags, V: fmt::Debug> fmt::Debug for FlagMap<T, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(T::SINGLE_FLAGS.iter().zip(self.values.as_ref()).filter_map(|(row, value)| {
+                value.as_ref().map(|value| (row.name, value))
+            }))
+            .finish()
+    }
+}
+
+impl<T: Flags, V> FromIterator<(T, V)> for FlagMap<T, V> {
+    /// Panics if a flag is not a single flag.
+    #[track_caller]
+    fn from_iter<I: IntoIterator<Item = (T, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<T: Flags, V> Extend<(T, V)> for FlagMap<T, V> {
+    /// Panics if a flag is not a single flag.
+    #[track_caller]
+    fn extend<I: IntoIterator<Item = (T, V)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|(flag, value)| {
+            self.insert(flag, value);
+        });
+    }
+}
+
+impl<'a, T: Flags, V> IntoIterator for &'a FlagMap<T, V> {
+    type Item = (T, &'a V);
+    type IntoIter = FlagMapIter<'a, T, V>;
+    #[inline]
+    fn into_iter(self) -> FlagMapIter<'a, T, V> {
+        self.iter()
+    }
+}
+
+impl<'a, T: Flags, V> IntoIterator for &'a mut FlagMap<T, V> {
+    type Item = (T, &'a mut V);
+    type IntoIter = FlagMapIterMut<'a, T, V>;
+    #[inline]
+    fn into_iter(self) -> FlagMapIterMut<'a, T, V> {
+        self.iter_mut()
+    }
+}
+
+/// An iterator over the flags and values of a [FlagMap].
+pub struct FlagMapIter<'a, T: Flags, V> {
+    rows: ::core::iter::Zip<::core::slice::Iter<'static, crate::FlagRow<T>>, ::core::slice::Iter<'a, Option<V>>>,
+}
+
+impl<'a, T: Flags, V> Iterator for FlagMapIter<'a, T, V> {
+    type Item = (T, &'a V);
+    
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.find_map(|(row, value)| value.as_ref().map(|value| (row.value, value)))
+    }
+}
+
+impl<T: Flags, V> FusedIterator for FlagMapIter<'_, T, V> {}
+
+/// An iterator over the flags and mutable values of a [FlagMap].
+pub struct FlagMapIterMut<'a, T: Flags, V> {
+    rows: ::core::iter::Zip<::core::slice::Iter<'static, crate::FlagRow<T>>, ::core::slice::IterMut<'a, Option<V>>>,
+}
+
+impl<'a, T: Flags, V> Iterator for FlagMapIterMut<'a, T, V> {
+    type Item = (T, &'a mut V);
+    
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rows.find_map(|(row, value)| value.as_mut().map(|value| (row.value, value)))
+    }
+}
+
+impl<T: Flags, V> FusedIterator for FlagMapIterMut<'_, T, V> {}
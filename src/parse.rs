@@ -0,0 +1,165 @@
+use core::fmt;
+
+use crate::Flags;
+
+/// The reason that parsing a flags expression failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseFlagsErrorKind {
+    /// The name is not a single or group flag, or the hex bits don't fit in the flags type.
+    UnknownFlag,
+    /// An operator was not followed by a flag name.
+    ExpectedFlag,
+    /// A flag name was not followed by an operator.
+    ExpectedOperator,
+    /// The character can not appear in a flags expression.
+    InvalidCharacter,
+}
+
+/// The error returned when parsing a flags expression fails.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParseFlagsError {
+    kind: ParseFlagsErrorKind,
+    token: String,
+    offset: usize,
+}
+
+impl ParseFlagsError {
+    #[must_use]
+    #[inline]
+    pub fn new(kind: ParseFlagsErrorKind, token: &str, offset: usize) -> Self {
+        Self {
+            kind,
+            token: token.to_owned(),
+            offset,
+        }
+    }
+
+    #[must_use]
+    #[inline]
+    pub const fn kind(&self) -> ParseFlagsErrorKind {
+        self.kind
+    }
+
+    /// The offending token. This is empty if the input ended unexpectedly.
+    #[must_use]
+    #[inline]
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// The byte offset of the offending token in the input.
+    #[must_use]
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for ParseFlagsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let token = &self.token;
+        let offset = self.offset;
+        match self.kind {
+            ParseFlagsErrorKind::UnknownFlag => write!(f, "unknown flag `{token}` at offset {offset}"),
+            ParseFlagsErrorKind::ExpectedFlag if token.is_empty() => write!(f, "expected flag name at offset {offset}, found end of input"),
+            ParseFlagsErrorKind::ExpectedFlag => write!(f, "expected flag name at offset {offset}, found `{token}`"),
+            ParseFlagsErrorKind::ExpectedOperator => write!(f, "expected `|`, `+`, or `-` at offset {offset}, found `{token}`"),
+            ParseFlagsErrorKind::InvalidCharacter => write!(f, "invalid character `{token}` at offset {offset}"),
+        }
+    }
+}
+
+impl core::error::Error for ParseFlagsError {}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Name(&'a str),
+    /// `|` or `+`
+    Add,
+    /// `-`
+    Remove,
+}
+
+struct Tokens<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Result<(usize, &'a str, Token<'a>), ParseFlagsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.source[self.offset..];
+        let trimmed = rest.trim_start();
+        let start = self.offset + (rest.len() - trimmed.len());
+        let first = trimmed.chars().next()?;
+        let len = match first {
+            '|' | '+' | '-' => 1,
+            ch if ch == '_' || ch.is_ascii_alphanumeric() => trimmed
+                .find(|ch: char| ch != '_' && !ch.is_ascii_alphanumeric())
+                .unwrap_or(trimmed.len()),
+            ch => {
+                let token = &trimmed[..ch.len_utf8()];
+                self.offset = self.source.len();
+                return Some(Err(ParseFlagsError::new(ParseFlagsErrorKind::InvalidCharacter, token, start)));
+            }
+        };
+        let text = &trimmed[..len];
+        self.offset = start + len;
+        let token = match first {
+            '|' | '+' => Token::Add,
+            '-' => Token::Remove,
+            _ => Token::Name(text),
+        };
+        Some(Ok((start, text, token)))
+    }
+}
+
+/// Parse a flags expression such as `ADMIN | LOBBY - CLEAR_LOG`.
+///
+/// The expression is a list of single or group flag names joined by operators,
+/// with an optional operator at the start. `|` and `+` add the flag that follows,
+/// and `-` removes it. Starting from `NONE`, each flag is applied from left to right.
+/// An empty (or all whitespace) string parses to `NONE`.
+///
+/// Raw bits can be written in hex instead of a name, such as `LOBBY | 0x800000`,
+/// which is how `Display` writes bits that are not part of any flag.
+pub fn parse_flags<T: Flags>(source: &str) -> Result<T, ParseFlagsError> {
+    let mut flags = T::NONE;
+    // The operator that comes before the next flag name.
+    let mut operator = None;
+    let mut after_name = false;
+    for next in (Tokens { source, offset: 0 }) {
+        let (offset, text, token) = next?;
+        match token {
+            Token::Name(name) => {
+                if after_name {
+                    return Err(ParseFlagsError::new(ParseFlagsErrorKind::ExpectedOperator, text, offset));
+                }
+                let flag = match name.strip_prefix("0x") {
+                    Some(digits) => crate::fmt::parse_hex::<T>(digits),
+                    None => T::try_find(name),
+                };
+                let Some(flag) = flag else {
+                    return Err(ParseFlagsError::new(ParseFlagsErrorKind::UnknownFlag, text, offset));
+                };
+                match operator.take() {
+                    Some(Token::Remove) => flags.remove(flag),
+                    _ => Flags::add(&mut flags, flag),
+                };
+                after_name = true;
+            }
+            Token::Add | Token::Remove => {
+                if operator.is_some() {
+                    return Err(ParseFlagsError::new(ParseFlagsErrorKind::ExpectedFlag, text, offset));
+                }
+                operator = Some(token);
+                after_name = false;
+            }
+        }
+    }
+    if operator.is_some() {
+        return Err(ParseFlagsError::new(ParseFlagsErrorKind::ExpectedFlag, "", source.len()));
+    }
+    Ok(flags)
+}
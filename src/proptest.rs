@@ -0,0 +1,51 @@
+//! [proptest] strategies for flags types, enabled with the `proptest` feature.
+//!
+//! Every flags type generated by [flags!](crate::flags) implements
+//! [proptest::arbitrary::Arbitrary] with [any_valid], so `any::<Perms>()` works.
+//!
+//! The strategies only set the bits of single flags, so values never have unused bits set
+//! (see `is_valid`). They shrink towards fewer flags.
+//!
+//! ```rust,ignore
+//! proptest! {
+//!     #[test]
+//!     fn moderators_can_kick(perms in vexillo::proptest::superset_of(Perms::MODERATOR)) {
+//!         assert!(can_kick(perms));
+//!     }
+//! }
+//! ```
+
+use ::proptest::{sample::subsequence, strategy::{BoxedStrategy, Strategy}};
+
+use crate::Flags;
+
+/// Values with `required` and any of the single flags in `allowed`.
+fn between<T: Flags>(required: T, allowed: T) -> BoxedStrategy<T> {
+    let optional = T::SINGLE_FLAGS.iter()
+        .map(|row| row.value)
+        .filter(|&flag| allowed.has_all(flag) && !required.has_all(flag))
+        .collect::<Vec<_>>();
+    let len = optional.len();
+    subsequence(optional, 0..=len)
+        .prop_map(move |flags| flags.into_iter().fold(required, T::with))
+        .boxed()
+}
+
+/// Any valid value of `T`.
+pub fn any_valid<T: Flags>() -> BoxedStrategy<T> {
+    between(T::NONE, T::ALL)
+}
+
+/// Values that only have flags of `mask`.
+///
+/// Bits of `mask` that are not part of a single flag are never set.
+pub fn subset_of<T: Flags>(mask: T) -> BoxedStrategy<T> {
+    between(T::NONE, mask)
+}
+
+/// Values that have all of the bits of `mask`, and any other flags.
+///
+/// The values are only valid if `mask` is valid.
+pub fn superset_of<T: Flags>(mask: T) -> BoxedStrategy<T> {
+    between(mask, T::ALL)
+}
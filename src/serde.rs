@@ -0,0 +1,178 @@
+//! `Serialize`/`Deserialize` support for flags types, enabled with the `serde` feature.
+//! 
+//! The impls are generated by [flags!](crate::flags), and the representation is chosen
+//! with the `serde` option:
+//! 
+//! ```rust,ignore
+//! #[vexillo(serde(repr = names, unknown = ignore))]
+//! pub struct Perms(pub [u8]);
+//! ```
+
+use core::{fmt, marker::PhantomData};
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer, de::{self, IgnoredAny, SeqAccess, Visitor}, ser::SerializeSeq};
+
+use crate::{Flags, fmt::{HexBits, parse_hex}};
+
+/// How a flags value is serialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Repr {
+    /// [Repr::Names] for human-readable formats, [Repr::Bytes] otherwise.
+    #[default]
+    Auto,
+    /// A sequence of single flag names, such as `["BAN_USER", "UNBAN_USER"]`.
+    /// 
+    /// Bits that are not part of any flag are written as one hex string at the end,
+    /// such as `"0x800000"`. Group names and hex strings are accepted when deserializing.
+    Names,
+    /// The little-endian bytes of the masks (see `to_le_bytes`).
+    Bytes,
+    /// The mask integers. A single integer when there is only one mask,
+    /// otherwise a sequence of integers, starting with the lowest mask.
+    Masks,
+}
+
+/// What deserialization does with names and bits that are not part of any flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Unknown {
+    /// Unknown names and bits are an error.
+    #[default]
+    Error,
+    /// Unknown names are skipped and unknown bits are cleared.
+    Ignore,
+    /// Unknown bits are kept. Unknown names are still an error since there are no bits to keep.
+    Preserve,
+}
+
+impl Repr {
+    #[inline]
+    fn resolve(self, human_readable: bool) -> Self {
+        match self {
+            Repr::Auto if human_readable => Repr::Names,
+            Repr::Auto => Repr::Bytes,
+            repr => repr,
+        }
+    }
+}
+
+/// Serialize `flags` with the representation `repr`.
+pub fn serialize<T: Flags, S: Serializer>(flags: T, repr: Repr, serializer: S) -> Result<S::Ok, S::Error> {
+    match repr.resolve(serializer.is_human_readable()) {
+        Repr::Auto | Repr::Names => {
+            let unknown = flags.without(T::ALL);
+            let has_unknown = unknown.count_ones() != 0;
+            let len = flags.and(T::ALL).count_ones() as usize + has_unknown as usize;
+            let mut seq = serializer.serialize_seq(Some(len))?;
+            flags.iter_names().try_for_each(|name| seq.serialize_element(name))?;
+            if has_unknown {
+                seq.serialize_element(&format_args!("{}", HexBits(unknown)))?;
+            }
+            seq.end()
+        }
+        Repr::Bytes => serializer.serialize_bytes(flags.to_le_bytes().as_ref()),
+        Repr::Masks if T::MASK_COUNT == 1 => flags.as_slice()[0].serialize(serializer),
+        Repr::Masks => serializer.collect_seq(flags.as_slice()),
+    }
+}
+
+/// Deserialize a flags value that was serialized with the representation `repr`.
+pub fn deserialize<'de, T: Flags, D: Deserializer<'de>>(deserializer: D, repr: Repr, unknown: Unknown) -> Result<T, D::Error> {
+    match repr.resolve(deserializer.is_human_readable()) {
+        Repr::Auto | Repr::Names => deserializer.deserialize_seq(NamesVisitor::<T>(unknown, PhantomData)),
+        Repr::Bytes => deserializer.deserialize_bytes(BytesVisitor::<T>(unknown, PhantomData)),
+        Repr::Masks if T::MASK_COUNT == 1 => {
+            let mut flags = T::NONE;
+            flags.as_mut_slice()[0] = T::MaskType::deserialize(deserializer)?;
+            check_unknown(flags, unknown)
+        }
+        Repr::Masks => deserializer.deserialize_tuple(T::MASK_COUNT, MasksVisitor::<T>(unknown, PhantomData)),
+    }
+}
+
+fn check_unknown<T: Flags, E: de::Error>(flags: T, unknown: Unknown) -> Result<T, E> {
+    let unknown_bits = flags.without(T::ALL);
+    match unknown {
+        Unknown::Error if unknown_bits.count_ones() != 0 => Err(E::custom(format_args!(
+            "unknown bits {}",
+            HexBits(unknown_bits),
+        ))),
+        Unknown::Ignore => Ok(flags.and(T::ALL)),
+        _ => Ok(flags),
+    }
+}
+
+struct NamesVisitor<T: Flags>(Unknown, PhantomData<T>);
+
+impl<'de, T: Flags> Visitor<'de> for NamesVisitor<T> {
+    type Value = T;
+    
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of flag names")
+    }
+    
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let mut flags = T::NONE;
+        while let Some(name) = seq.next_element::<String>()? {
+            if let Some(digits) = name.strip_prefix("0x") {
+                let Some(bits) = parse_hex::<T>(digits) else {
+                    return Err(de::Error::invalid_value(de::Unexpected::Str(&name), &"hex bits that fit in the flags type"));
+                };
+                Flags::add(&mut flags, check_unknown(bits, self.0)?);
+            } else if let Some(flag) = T::try_find(&name) {
+                Flags::add(&mut flags, flag);
+            } else if self.0 != Unknown::Ignore {
+                return Err(de::Error::custom(format_args!("unknown flag `{name}`")));
+            }
+        }
+        Ok(flags)
+    }
+}
+
+struct BytesVisitor<T: Flags>(Unknown, PhantomData<T>);
+
+impl<'de, T: Flags> Visitor<'de> for BytesVisitor<T> {
+    type Value = T;
+    
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{} little-endian bytes", size_of::<T>())
+    }
+    
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<T, E> {
+        let mut bytes = T::NONE.to_le_bytes();
+        if v.len() != bytes.as_ref().len() {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        bytes.as_mut().copy_from_slice(v);
+        check_unknown(T::from_le_bytes(bytes), self.0)
+    }
+    
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let mut bytes = T::NONE.to_le_bytes();
+        let len = bytes.as_ref().len();
+        for (i, byte) in bytes.as_mut().iter_mut().enumerate() {
+            *byte = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(len + 1, &self));
+        }
+        check_unknown(T::from_le_bytes(bytes), self.0)
+    }
+}
+
+struct MasksVisitor<T: Flags>(Unknown, PhantomData<T>);
+
+impl<'de, T: Flags> Visitor<'de> for MasksVisitor<T> {
+    type Value = T;
+    
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{} mask integers", T::MASK_COUNT)
+    }
+    
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let mut flags = T::NONE;
+        for (i, mask) in flags.as_mut_slice().iter_mut().enumerate() {
+            *mask = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        check_unknown(flags, self.0)
+    }
+}
@@ -11,6 +11,8 @@ use ::core::ops::{
     Range,
 };
 use vexmacro::const_binary_search_fn;
+use crate::ConvertedFlags;
+use crate::iter::{DecomposeNamed, Iter, IterGroups, IterIndices, IterNames};
 
 pub trait Flags: 'static
     + Sized
@@ -37,14 +39,20 @@ pub trait Flags: 'static
     + Index<usize, Output = bool>
 {
     /// The type that is used for the internal bitmasks.
-    type MaskType;
+    type MaskType: crate::internal::MustBeUnsignedInt;
     type MasksArrayType;
-    type BytesArrayType;
+    type BytesArrayType: AsRef<[u8]> + AsMut<[u8]>;
+    /// `[Option<V>; SINGLE_FLAG_COUNT]`, the storage of a [FlagMap](crate::FlagMap).
+    type MapArrayType<V>: AsRef<[Option<V>]> + AsMut<[Option<V>]>;
     
     /// The total number of bits for this type. This is equal to `size_of::<Self> * 8`.
     const BITS: u32;
+    /// The number of bits that are assigned to single flags.
     const USED_BITS: u32;
+    /// The number of bits that are not assigned to single flags, including reserved bits and gaps.
     const UNUSED_BITS: u32;
+    /// One past the highest bit index of a single flag or reserved bit. Bits at or above this index are padding.
+    const INDEX_SPAN: u32;
     /// The total number of bits for the mask type.
     const MASK_BITS: u32;
     /// The total number of bytes for the mask type.
@@ -63,18 +71,27 @@ pub trait Flags: 'static
     /// An instance with all of the bits set to 1.
     const ALL: Self;
     
-    /// A table of all flags ordered first by [single, group], then ordered by declaration order.
+    /// A table of all flags ordered first by [single, group], then single flags are ordered
+    /// by bit index and group flags are ordered by declaration order.
     /// 
     /// - `FLAGS_TABLE[..SINGLE_FLAG_COUNT]` are the single flags.
-    /// - `FLAGS_TALE[SINGLE_FLAG_COUNT..]` are the group flags.
+    /// - `FLAGS_TABLE[SINGLE_FLAG_COUNT..]` are the group flags.
     const FLAGS_TABLE: &'static [FlagRow<Self>];
     
+    /// The single flags in `FLAGS_TABLE`, ordered by bit index.
     const SINGLE_FLAGS: &'static [FlagRow<Self>];
+    /// The group flags in `FLAGS_TABLE`.
     const GROUP_FLAGS: &'static [FlagRow<Self>];
+    /// Indices into `FLAGS_TABLE` ordered by bit count (descending), then by table order.
     const BIT_ORDERED_FLAGS_TABLE: &'static [FlagIndex];
+    /// Indices into `FLAGS_TABLE` ordered by name.
     const ORDERED_FLAGS_TABLE: &'static [FlagIndex];
+    /// Indices into `FLAGS_TABLE` of the single flags, ordered by name.
     const ORDERED_SINGLE_FLAG_INDICES: &'static [FlagIndex];
+    /// Indices into `FLAGS_TABLE` of the group flags, ordered by name.
     const ORDERED_GROUP_FLAG_INDICES: &'static [FlagIndex];
+    /// The deprecated names of flags, declared with `alias OLD = NEW`, ordered by name.
+    const ALIASES: &'static [FlagAlias];
     
     /// Create a new instance with none of the bits set.
     fn new() -> Self;
@@ -86,14 +103,32 @@ pub trait Flags: 'static
     fn union(flags: &[Self]) -> Self;
     /// Create a union of all `flags` without all `removals`.
     fn union_without(flags: &[Self], removals: &[Self]) -> Self;
-    /// Try to find a flag by its name. Returns [None] if the flag was not found.
-    fn try_find(name: &str) -> Option<Self>;
+    /// Try to find a flag by its name or alias. Returns [None] if the flag was not found.
+    fn try_find(name: &str) -> Option<Self> {
+        let rows = FlagRows::new(Self::FLAGS_TABLE);
+        rows.find(name, Self::ORDERED_FLAGS_TABLE)
+            .or_else(|| rows.find_alias(name, Self::ALIASES))
+            .map(|row| row.value)
+    }
     /// Find a flag by its name. Panics if the flag was not found.
-    fn find(name: &str) -> Self;
+    #[track_caller]
+    fn find(name: &str) -> Self {
+        match Self::try_find(name) {
+            Some(flag) => flag,
+            None => panic!("Flag by that name was not found."),
+        }
+    }
     /// Find a flag by its name. Returns `default` if the flag was not found.
-    fn find_or(name: &str, default: Self) -> Self;
+    fn find_or(name: &str, default: Self) -> Self {
+        Self::try_find(name).unwrap_or(default)
+    }
     /// Find a flag by its name. Returns `NONE` if the flag was not found.
-    fn find_or_none(name: &str) -> Self;
+    fn find_or_none(name: &str) -> Self {
+        Self::try_find(name).unwrap_or(Self::NONE)
+    }
+    /// Create the storage of a [FlagMap](crate::FlagMap), where the value of the
+    /// single flag `SINGLE_FLAGS[i]` is `init(i)`.
+    fn map_array_from_fn<V, Init: FnMut(usize) -> Option<V>>(init: Init) -> Self::MapArrayType<V>;
     /// Return the number of ones in the binary representation of `self`.
     fn count_ones(self) -> u32;
     /// Return the number of zeros in the binary representation of `self`.
@@ -169,9 +204,52 @@ pub trait Flags: 'static
     /// Bitwise NIMPLY.
     fn nimply(self, other: Self) -> Self;
     /// Test for equality.
-    fn eq(self, other: Self) -> Self;
+    fn eq(self, other: Self) -> bool;
     /// Test for inequality.
-    fn ne(self, other: Self) -> Self;
+    fn ne(self, other: Self) -> bool;
+    /// Iterate over the single flags that are set in `self`.
+    #[inline]
+    fn iter(self) -> Iter<Self> {
+        Iter::new(self)
+    }
+    /// Iterate over the bit indices that are set in `self`.
+    #[inline]
+    fn iter_indices(self) -> IterIndices<Self> {
+        IterIndices::new(self)
+    }
+    /// Iterate over the names of the single flags that are set in `self`.
+    #[inline]
+    fn iter_names(self) -> IterNames<Self> {
+        IterNames::new(self)
+    }
+    /// Iterate over the group flags that are fully contained in `self`.
+    #[inline]
+    fn iter_groups(self) -> IterGroups<Self> {
+        IterGroups::new(self)
+    }
+    /// Decompose `self` into the largest named flags that it contains.
+    #[inline]
+    fn decompose_named(self) -> DecomposeNamed<Self> {
+        DecomposeNamed::new(self)
+    }
+    /// Convert `self` to the flags type `U` by name. Each single flag of `self` becomes
+    /// the single flag of `U` with the same name. See [ConvertedFlags].
+    ///
+    /// [convert_by_name!](crate::convert_by_name) does the same at compile time.
+    fn convert_by_name<U: Flags>(self) -> ConvertedFlags<Self, U> {
+        let mut converted = ConvertedFlags {
+            value: U::NONE,
+            unmatched: self,
+        };
+        let targets = FlagRows::new(U::FLAGS_TABLE);
+        for row in Self::SINGLE_FLAGS.iter().filter(|row| self.has_all(row.value)) {
+            if let Some(target) = targets.find(row.name, U::ORDERED_SINGLE_FLAG_INDICES) {
+                Flags::add(&mut converted.value, target.value);
+                Flags::remove(&mut converted.unmatched, row.value);
+            }
+        }
+        converted
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -193,18 +271,102 @@ impl FlagIndex {
     }
 }
 
+/// Whether a flag is a single-bit flag or a group of flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FlagKind {
+    Single,
+    Group,
+}
+
+/// The error returned when flags are converted to a single flag enum
+/// (see the `flag_enum` option of [flags](crate::flags)), but the flags
+/// are not exactly one single flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NotSingleFlagError;
+
+impl ::core::fmt::Display for NotSingleFlagError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str("flags are not exactly one single flag")
+    }
+}
+
+impl ::core::error::Error for NotSingleFlagError {}
+
+/// The error returned by `validate` when a value has more than one flag of an exclusive
+/// set (see `exclusive [...]` in [flags](crate::flags)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Conflict<T: Flags> {
+    /// The flags of the exclusive set that are set. There are at least two.
+    pub flags: T,
+    /// Every flag of the exclusive set.
+    pub set: T,
+}
+
+impl<T: Flags> Conflict<T> {
+    #[must_use]
+    #[inline(always)]
+    pub const fn new(flags: T, set: T) -> Self {
+        Self { flags, set }
+    }
+}
+
+impl<T: Flags> ::core::fmt::Display for Conflict<T> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        f.write_str("mutually exclusive flags are set:")?;
+        for (i, name) in self.flags.iter_names().enumerate() {
+            let separator = if i == 0 { " " } else { " | " };
+            write!(f, "{separator}{name}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Flags> ::core::error::Error for Conflict<T> {}
+
+/// A deprecated name of a flag, declared with `alias OLD = NEW` in the const block.
+/// 
+/// Aliases are found by name, but they are not rows of the flag table, so they are
+/// never listed or formatted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FlagAlias {
+    pub name: &'static str,
+    /// The index of the flag in the flag table.
+    pub target: FlagIndex,
+}
+
+impl FlagAlias {
+    #[must_use]
+    #[inline(always)]
+    pub const fn new(name: &'static str, target: FlagIndex) -> Self {
+        Self { name, target }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct FlagRow<T: Flags> {
     pub name: &'static str,
     pub value: T,
+    /// The bit index of a single flag. Unused for groups.
+    index: u32,
     sub_flag_indices: Option<&'static [FlagIndex]>,
+    deprecated: bool,
+    ancestors: &'static [T],
+    children: &'static [T],
 }
 
 impl<T: Flags> FlagRow<T> {
     #[must_use]
     #[inline(always)]
-    pub const fn single(name: &'static str, value: T) -> Self {
-        Self { name, value, sub_flag_indices: None }
+    pub const fn single(name: &'static str, value: T, index: u32) -> Self {
+        Self {
+            name,
+            value,
+            index,
+            sub_flag_indices: None,
+            deprecated: false,
+            ancestors: &[],
+            children: &[],
+        }
     }
     
     #[must_use]
@@ -217,10 +379,115 @@ impl<T: Flags> FlagRow<T> {
         Self {
             name,
             value,
+            index: 0,
             sub_flag_indices: Some(sub_flag_indices),
+            deprecated: false,
+            ancestors: &[],
+            children: &[],
+        }
+    }
+    
+    /// Set where the flag is declared in the const block. `ancestors` are the groups
+    /// that the flag is declared in, from its parent outwards, and `children` are
+    /// the flags declared directly in the group, in declaration order.
+    #[must_use]
+    #[inline(always)]
+    pub const fn with_hierarchy(mut self, ancestors: &'static [T], children: &'static [T]) -> Self {
+        self.ancestors = ancestors;
+        self.children = children;
+        self
+    }
+    
+    /// Mark the flag as `#[deprecated]`.
+    #[must_use]
+    #[inline(always)]
+    pub const fn with_deprecated(mut self) -> Self {
+        self.deprecated = true;
+        self
+    }
+    
+    /// Whether the flag is `#[deprecated]`.
+    #[must_use]
+    #[inline(always)]
+    pub const fn is_deprecated(&self) -> bool {
+        self.deprecated
+    }
+    
+    /// The group that the flag is declared in, or `None` if it is declared at the top level.
+    #[must_use]
+    #[inline(always)]
+    pub const fn parent(&self) -> Option<T> {
+        match self.ancestors.first() {
+            Some(&parent) => Some(parent),
+            None => None,
+        }
+    }
+    
+    /// The groups that the flag is declared in, from its parent to the top level.
+    #[must_use]
+    #[inline(always)]
+    pub const fn ancestors(&self) -> &'static [T] {
+        self.ancestors
+    }
+    
+    /// The flags and groups declared directly in the group, in declaration order.
+    /// Single flags have no children.
+    #[must_use]
+    #[inline(always)]
+    pub const fn children(&self) -> &'static [T] {
+        self.children
+    }
+    
+    /// The number of groups that the flag is declared in. Flags at the top level have a depth of 0.
+    #[must_use]
+    #[inline(always)]
+    pub const fn depth(&self) -> usize {
+        self.ancestors.len()
+    }
+    
+    #[must_use]
+    #[inline(always)]
+    pub const fn kind(&self) -> FlagKind {
+        if self.sub_flag_indices.is_some() {
+            FlagKind::Group
+        } else {
+            FlagKind::Single
+        }
+    }
+    
+    #[must_use]
+    #[inline(always)]
+    pub const fn is_single(&self) -> bool {
+        self.sub_flag_indices.is_none()
+    }
+    
+    #[must_use]
+    #[inline(always)]
+    pub const fn is_group(&self) -> bool {
+        self.sub_flag_indices.is_some()
+    }
+    
+    /// The bit index of a single flag. Groups have no bit index, so this is
+    /// only meaningful when the row is known to be a single flag.
+    #[doc(hidden)]
+    #[must_use]
+    #[inline(always)]
+    pub const fn bit_index(&self) -> u32 {
+        self.index
+    }
+    
+    /// The bit index of a single flag, or `None` for a group.
+    #[must_use]
+    #[inline(always)]
+    pub const fn index(&self) -> Option<u32> {
+        if self.sub_flag_indices.is_none() {
+            Some(self.index)
+        } else {
+            None
         }
     }
     
+    /// The number of bits set in `value`.
     #[must_use]
     #[inline(always)]
     pub const fn bits(&self) -> u32 {
@@ -231,6 +498,8 @@ impl<T: Flags> FlagRow<T> {
         }
     }
     
+    /// Indices (into the flag table) of the single flags that make up this flag.
+    /// Single flags have no sub flags.
     #[must_use]
     #[inline(always)]
     pub const fn sub_flag_indices(&self) -> &'static [FlagIndex] {
@@ -251,13 +520,22 @@ pub struct FlagGroupInfo {
     pub index: u16,
 }
 
+/// Static metadata for every flag of a flags type, generated as `TABLES`.
+/// 
+/// `rows` are ordered first by [single, group], then single flags are ordered by
+/// bit index and group flags by declaration order. All of the index arrays are
+/// indices into `rows`.
 #[derive(Clone)]
 pub struct FlagTables<T: Flags, const TABLE_LEN: usize, const SINGLE_COUNT: usize, const GROUP_COUNT: usize> {
     pub rows: [FlagRow<T>; TABLE_LEN],
     pub name_ordered_row_indices: [FlagIndex; TABLE_LEN],
+    /// All rows ordered by bit count (descending), then by row order.
+    pub bit_ordered_row_indices: [FlagIndex; TABLE_LEN],
     pub name_ordered_single_indices: [FlagIndex; SINGLE_COUNT],
     pub name_ordered_group_indices: [FlagIndex; GROUP_COUNT],
     pub bit_ordered_group_indices: [FlagIndex; GROUP_COUNT],
+    /// The deprecated names of flags, ordered by name. These are not rows.
+    pub aliases: &'static [FlagAlias],
 }
 
 #[derive(Clone, Copy)]
@@ -283,6 +561,78 @@ impl<T: Flags> FlagRows<T> {
     pub const fn row(&self, index: usize) -> &'static FlagRow<T> {
         &self.rows[index]
     }
+    
+    #[inline(always)]
+    const fn name_search_cmp(name: &str, rhs_index: FlagIndex, context: &[FlagRow<T>]) -> ::core::cmp::Ordering {
+        crate::internal::const_cmp_str(name, context[rhs_index.index()].name)
+    }
+    
+    const_binary_search_fn!(
+        use Self::name_search_cmp;
+        #[must_use]
+        #[inline(always)]
+        const fn name_binary_search(ref str, static FlagIndex, context: &[FlagRow<T>]) -> Option
+    );
+    
+    /// Find the single flag with the bit index `index`. `self` must be the single flags, ordered by bit index.
+    #[must_use]
+    pub const fn find_index(&self, index: u32) -> Option<&'static FlagRow<T>> {
+        let mut lo = 0usize;
+        let mut hi = self.rows.len();
+        while lo < hi {
+            let mid = (hi - lo) / 2 + lo;
+            let row = &self.rows[mid];
+            if row.index == index {
+                return Some(row);
+            } else if row.index < index {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        None
+    }
+    
+    /// Returns the first index in `bit_ordered` of a row where `row.bits() <= bits`, or
+    /// `bit_ordered.len()` if there is no such row. `bit_ordered` must be indices into
+    /// `self.rows` ordered by bit count (descending).
+    #[must_use]
+    pub const fn upper_bit_count_search(&self, bits: u32, bit_ordered: &[FlagIndex]) -> usize {
+        let mut lo = 0usize;
+        let mut hi = bit_ordered.len();
+        while lo < hi {
+            let mid = (hi - lo) / 2 + lo;
+            if self.rows[bit_ordered[mid].index()].bits() > bits {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+    
+    /// Find the row named `name`. `name_ordered` must be indices into `self.rows` ordered by name.
+    #[must_use]
+    pub const fn find(&self, name: &str, name_ordered: &[FlagIndex]) -> Option<&'static FlagRow<T>> {
+        match Self::name_binary_search(name, name_ordered, self.rows) {
+            Some(index) => Some(&self.rows[name_ordered[index].index()]),
+            None => None,
+        }
+    }
+    
+    /// Find the row that the alias `name` refers to. The targets of `aliases` must be indices into `self.rows`.
+    #[must_use]
+    pub const fn find_alias(&self, name: &str, aliases: &[FlagAlias]) -> Option<&'static FlagRow<T>> {
+        // There are usually only a few aliases, so they are searched in order.
+        let mut i = 0;
+        while i < aliases.len() {
+            if crate::internal::const_cmp_str(name, aliases[i].name).is_eq() {
+                return Some(&self.rows[aliases[i].target.index()]);
+            }
+            i += 1;
+        }
+        None
+    }
 }
 
 impl<
@@ -357,43 +707,75 @@ impl<
         self.row(index).value
     }
     
+    #[must_use]
     #[inline(always)]
-    const fn rev_bit_count_search_cmp(lhs: u32, rhs_index: FlagIndex, context: &[FlagRow<T>]) -> ::core::cmp::Ordering {
-        use ::core::cmp::Ordering::*;
-        let rhs = context[rhs_index.index()].bits();
-        if lhs > rhs {
-            Less
-        } else if lhs < rhs {
-            Greater
-        } else {
-            Equal
+    pub const fn kind(&'static self, index: u16) -> FlagKind {
+        self.row(index).kind()
+    }
+    
+    /// Find a single or group flag by its name or alias.
+    #[must_use]
+    #[inline]
+    pub const fn find(&'static self, name: &str) -> Option<&'static FlagRow<T>> {
+        let rows = FlagRows::new(&self.rows);
+        match rows.find(name, &self.name_ordered_row_indices) {
+            Some(row) => Some(row),
+            None => rows.find_alias(name, self.aliases),
         }
     }
     
-    const_binary_search_fn!(
-        use Self::rev_bit_count_search_cmp;
-        #[must_use]
-        #[inline(always)]
-        const fn bit_count_binary_search(static u32, static FlagIndex, context: &[FlagRow<T>]) -> usize
-    );
+    /// Find a single flag by its name or alias.
+    #[must_use]
+    #[inline]
+    pub const fn find_single(&'static self, name: &str) -> Option<&'static FlagRow<T>> {
+        let rows = FlagRows::new(&self.rows);
+        match rows.find(name, &self.name_ordered_single_indices) {
+            Some(row) => Some(row),
+            None => match rows.find_alias(name, self.aliases) {
+                Some(row) if row.is_single() => Some(row),
+                _ => None,
+            },
+        }
+    }
+    
+    /// Find the single flag with the bit index `index`.
+    #[must_use]
+    #[inline]
+    pub const fn find_index(&'static self, index: u32) -> Option<&'static FlagRow<T>> {
+        self.singles().find_index(index)
+    }
+    
+    /// Find a group flag by its name or alias.
+    #[must_use]
+    #[inline]
+    pub const fn find_group(&'static self, name: &str) -> Option<&'static FlagRow<T>> {
+        let rows = FlagRows::new(&self.rows);
+        match rows.find(name, &self.name_ordered_group_indices) {
+            Some(row) => Some(row),
+            None => match rows.find_alias(name, self.aliases) {
+                Some(row) if row.is_group() => Some(row),
+                _ => None,
+            },
+        }
+    }
     
     /// Returns the first index of the row in `self.bit_ordered_group_indices` where `row.bits <= bits`.
     #[inline]
     #[must_use]
     #[track_caller]
-    const fn upper_bit_count_search(&'static self, bits: u32, range: Range<usize>) -> usize {
+    pub const fn upper_bit_count_search(&'static self, bits: u32, range: Range<usize>) -> usize {
         let start = range.start;
-        Self::bit_count_binary_search(
+        FlagRows::new(&self.rows).upper_bit_count_search(
             bits,
             crate::internal::subslice(&self.bit_ordered_group_indices, range),
-            &self.rows
         ) + start
     }
     
+    /// Returns the first index in `self.bit_ordered_group_indices` where `row.bits <= bits`.
     #[inline]
     #[must_use]
     #[track_caller]
-    const fn upper_bit_count_search_full(&'static self, bits: u32) -> usize {
+    pub const fn upper_bit_count_search_full(&'static self, bits: u32) -> usize {
         self.upper_bit_count_search(
             bits,
             0..self.bit_ordered_group_indices.len()
@@ -264,6 +264,7 @@ fn test_functions() {
 }
 
 #[test]
+#[allow(unused_variables)]
 fn test_to_and_from_bytes() {
     flags!{
         struct F(pub [u16]);
@@ -329,6 +330,1052 @@ fn test_flag_updates_and_groups() {
     }
 }
 
+#[test]
+fn test_flags_trait() {
+    fn audit<F: vexillo::Flags>(flags: F) -> Vec<&'static str> {
+        F::SINGLE_FLAGS
+            .iter()
+            .filter(|row| flags.has_all(row.value))
+            .map(|row| row.name)
+            .collect()
+    }
+    assert_eq!(
+        audit(Perms::BAN_USER | Perms::LOBBY),
+        ["BAN_USER", "LOBBY"],
+    );
+    assert_eq!(<Perms as vexillo::Flags>::TOTAL_FLAG_COUNT, 25);
+    assert_eq!(<Perms as vexillo::Flags>::SINGLE_FLAGS.len(), Perms::SINGLE_FLAG_COUNT);
+    assert_eq!(<Perms as vexillo::Flags>::GROUP_FLAGS.len(), Perms::GROUP_FLAG_COUNT);
+    let owner = &<Perms as vexillo::Flags>::GROUP_FLAGS[5];
+    assert_eq!(owner.name, "OWNER");
+    assert_eq!(owner.bits(), 19);
+    // Largest group first.
+    let largest = <Perms as vexillo::Flags>::BIT_ORDERED_FLAGS_TABLE[0];
+    assert_eq!(<Perms as vexillo::Flags>::FLAGS_TABLE[largest.index()].name, "OWNER");
+    assert_eq!(<Perms as vexillo::Flags>::find("MOD"), Perms::MOD);
+    assert_eq!(<Perms as vexillo::Flags>::try_find("NOT_A_FLAG"), None);
+    // Overridden functions are still reachable through the trait.
+    assert_eq!(<Flags as vexillo::Flags>::not(Flags::NONE), Flags::ALL);
+    assert_eq!(<Flags as vexillo::Flags>::ALL, Flags::ALL);
+}
+
+#[test]
+fn test_tables() {
+    use vexillo::FlagKind;
+    let tables = &Perms::TABLES;
+    assert_eq!(tables.rows.len(), Perms::TOTAL_FLAG_COUNT);
+    assert_eq!(tables.singles().rows.len(), Perms::SINGLE_FLAG_COUNT);
+    assert_eq!(tables.groups().rows.len(), Perms::GROUP_FLAG_COUNT);
+    for (index, row) in tables.singles().rows.iter().enumerate() {
+        assert_eq!(row.kind(), FlagKind::Single);
+        assert_eq!(row.value, Perms::from_index(index as u32));
+        assert!(row.sub_flag_indices().is_empty());
+    }
+    let guest = tables.find_group("GUEST").unwrap();
+    assert_eq!(guest.kind(), FlagKind::Group);
+    assert_eq!(guest.value, Perms::GUEST);
+    let sub_flags = guest.sub_flag_indices()
+        .iter()
+        .map(|index| tables.rows[index.index()].name)
+        .collect::<Vec<_>>();
+    assert_eq!(sub_flags, ["LOBBY", "MESSAGE_MODS"]);
+    assert!(tables.find_single("GUEST").is_none());
+    assert_eq!(tables.find_single("LOBBY").unwrap().value, Perms::LOBBY);
+    // Each group's sub flags make up the group's value.
+    for row in tables.groups().rows {
+        let union = row.sub_flag_indices()
+            .iter()
+            .fold(Perms::NONE, |union, index| union | tables.rows[index.index()].value);
+        assert_eq!(union, row.value);
+        assert_eq!(row.bits(), row.value.count_ones());
+    }
+    let names = tables.name_ordered_row_indices
+        .iter()
+        .map(|index| tables.rows[index.index()].name)
+        .collect::<Vec<_>>();
+    let mut sorted = names.clone();
+    sorted.sort();
+    assert_eq!(names, sorted);
+    let bit_ordered = tables.bit_ordered_group_indices
+        .iter()
+        .map(|index| tables.rows[index.index()].name)
+        .collect::<Vec<_>>();
+    assert_eq!(bit_ordered, ["OWNER", "ADMIN", "SUPER", "MOD", "USER", "GUEST"]);
+}
+
+#[test]
+fn test_find() {
+    const BAN: Perms = Perms::find("BAN_USER");
+    assert_eq!(BAN, Perms::BAN_USER);
+    assert_eq!(Perms::try_find("MOD"), Some(Perms::MOD));
+    assert_eq!(Perms::try_find("BAN_USR"), None);
+    assert_eq!(Perms::try_find(""), None);
+    assert_eq!(Perms::find_or("BAN_USR", Perms::GUEST), Perms::GUEST);
+    assert_eq!(Perms::find_or_none("BAN_USR"), Perms::NONE);
+    for row in <Perms as vexillo::Flags>::FLAGS_TABLE {
+        assert_eq!(Perms::try_find(row.name), Some(row.value));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_find_panics() {
+    let _ = Perms::find("BAN_USR");
+}
+
+#[test]
+fn test_iterators() {
+    // Perms uses u8 masks, so these flags span all three masks.
+    let perms = perms!(REVOKE_ADMIN, RESTART_SERVER, GRANT_MOD, MOD_CHANNELS, MESSAGE_MODS);
+    let indices = perms.iter_indices().collect::<Vec<_>>();
+    assert_eq!(indices, [1, 9, 10, 12, 18]);
+    let mut rev = perms.iter_indices().rev().collect::<Vec<_>>();
+    rev.reverse();
+    assert_eq!(rev, indices);
+    let mut iter = perms.iter_indices();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(18));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.collect::<Vec<_>>(), [9, 10, 12]);
+    
+    assert_eq!(perms.iter().fold(Perms::NONE, |acc, flag| acc | flag), perms);
+    assert!(perms.iter().all(|flag| flag.count_ones() == 1));
+    assert_eq!(perms.into_iter().count(), 5);
+    assert_eq!(
+        perms.iter_names().collect::<Vec<_>>(),
+        ["REVOKE_ADMIN", "RESTART_SERVER", "GRANT_MOD", "MOD_CHANNELS", "MESSAGE_MODS"],
+    );
+    assert_eq!(Perms::NONE.iter().next(), None);
+    
+    let groups = (Perms::MOD | Perms::CLEAR_LOG)
+        .iter_groups()
+        .map(|row| row.name)
+        .collect::<Vec<_>>();
+    assert_eq!(groups, ["GUEST", "USER", "MOD"]);
+    let groups = Perms::OWNER.iter_groups().rev().map(|row| row.value).collect::<Vec<_>>();
+    assert_eq!(groups.len(), Perms::GROUP_FLAG_COUNT);
+    assert_eq!(groups[0], Perms::OWNER);
+    
+    let mut count = 0;
+    for flag in Flags::SUBGROUP1 {
+        assert!(Flags::SUBGROUP1.has_all(flag));
+        count += 1;
+    }
+    assert_eq!(count, 3);
+}
+
+#[test]
+fn test_decompose_named() {
+    fn names(perms: Perms) -> Vec<&'static str> {
+        perms.decompose_named().map(|row| row.name).collect()
+    }
+    assert_eq!(names(Perms::ADMIN | Perms::CLEAR_LOG), ["ADMIN", "CLEAR_LOG"]);
+    assert_eq!(names(Perms::OWNER), ["OWNER"]);
+    assert_eq!(names(Perms::MOD - Perms::BAN_USER), ["USER", "MOD_CHANNELS", "UNBAN_USER", "APPROVE_USER"]);
+    assert_eq!(names(Perms::GUEST | Perms::GRANT_ADMIN), ["GUEST", "GRANT_ADMIN"]);
+    assert!(names(Perms::NONE).is_empty());
+    for perms in [Perms::ADMIN | Perms::LOBBY, Perms::SUPER - Perms::GUEST, Perms::ALL] {
+        let union = perms.decompose_named().fold(Perms::NONE, |union, row| union | row.value);
+        assert_eq!(union, perms);
+    }
+    // Overlapping groups are not chosen greedily.
+    flags!(
+        struct Overlapping([u8]);
+        const {
+            ABCD: [
+                A
+                B
+                C
+                D
+            ]
+            ABC: [+A +B +C]
+            DEG: [
+                +D
+                E
+                G
+            ]
+        }
+    );
+    let names = |flags: Overlapping| flags.decompose_named().map(|row| row.name).collect::<Vec<_>>();
+    assert_eq!(names(Overlapping::ABC | Overlapping::DEG), ["ABC", "DEG"]);
+    assert_eq!(names(Overlapping::ABCD | Overlapping::E), ["ABCD", "E"]);
+    // Of the covers with the fewest names, the one where the groups overlap the least is used.
+    assert_eq!(names(Overlapping::ABCD | Overlapping::DEG), ["ABC", "DEG"]);
+    assert_eq!(names(Overlapping::ABCD | Overlapping::G), ["ABCD", "G"]);
+    // Groups overlap when that takes fewer names.
+    flags!(
+        struct Shared([u8]);
+        const {
+            ABC: [
+                A
+                B
+                C
+            ]
+            CDE: [
+                +C
+                D
+                E
+            ]
+        }
+    );
+    let decomposed = Shared::ALL.decompose_named().map(|row| row.name).collect::<Vec<_>>();
+    assert_eq!(decomposed, ["ABC", "CDE"]);
+    // Single flags are preferred over groups with a single bit.
+    flags!(
+        struct F([u8]);
+        const {
+            F0
+            FIRST: [+F0]
+            #[allow(vexillo::empty)]
+            EMPTY: []
+        }
+    );
+    let decomposed = F::F0.decompose_named().map(|row| row.name).collect::<Vec<_>>();
+    assert_eq!(decomposed, ["F0"]);
+}
+
+#[test]
+fn test_fmt() {
+    let perms = Perms::BAN_USER | Perms::UNBAN_USER;
+    assert_eq!(perms.to_string(), "BAN_USER | UNBAN_USER");
+    assert_eq!(format!("{perms:?}"), "Perms(BAN_USER | UNBAN_USER)");
+    assert_eq!(format!("{perms:#?}"), "Perms(\n    BAN_USER |\n    UNBAN_USER\n)");
+    assert_eq!(Perms::NONE.to_string(), "");
+    assert_eq!(format!("{:?}", Perms::NONE), "Perms()");
+    assert_eq!(format!("{:#?}", Perms::NONE), "Perms()");
+    // Unknown bits are written in hex after the names.
+    let mut unknown = Perms::LOBBY;
+    unknown.masks[2] |= 0x80;
+    assert_eq!(unknown.to_string(), "LOBBY | 0x800000");
+    assert_eq!(format!("{:?}", unknown.without(Perms::LOBBY)), "Perms(0x800000)");
+    // Nested pretty printing indents each flag.
+    assert_eq!(format!("{:#?}", Some(Perms::GUEST)), "Some(\n    Perms(\n        LOBBY |\n        MESSAGE_MODS\n    ),\n)");
+    
+    flags!(
+        #[vexillo(debug = raw, display = groups)]
+        struct Grouped([u8]);
+        const {
+            A
+            AB: [+A B]
+            C
+        }
+    );
+    assert_eq!(Grouped::ALL.to_string(), "AB | C");
+    assert_eq!((Grouped::A | Grouped::C).to_string(), "A | C");
+    assert_eq!(format!("{:?}", Grouped::AB), "Grouped { masks: [3] }");
+    
+    // Display output, including unknown bits, parses back to the same value.
+    for flags in [Perms::NONE, Perms::GUEST | Perms::BAN_USER, unknown, unknown.without(Perms::LOBBY)] {
+        assert_eq!(flags.to_string().parse::<Perms>(), Ok(flags));
+    }
+    let mut grouped_unknown = Grouped::AB;
+    grouped_unknown.masks[0] |= 0x80;
+    assert_eq!(grouped_unknown.to_string(), "AB | 0x80");
+    assert_eq!(grouped_unknown.to_string().parse::<Grouped>(), Ok(grouped_unknown));
+}
+
+#[test]
+fn test_from_str() {
+    assert_eq!("ADMIN | LOBBY - CLEAR_LOG".parse::<Perms>(), Ok((Perms::ADMIN | Perms::LOBBY) - Perms::CLEAR_LOG));
+    assert_eq!("".parse::<Perms>(), Ok(Perms::NONE));
+    assert_eq!("  \t".parse::<Perms>(), Ok(Perms::NONE));
+    assert_eq!("-GUEST+LOBBY".parse::<Perms>(), Ok(Perms::LOBBY));
+    assert_eq!("  + MOD -BAN_USER ".parse::<Perms>(), Ok(Perms::MOD - Perms::BAN_USER));
+    // Applied from left to right.
+    assert_eq!("GUEST - LOBBY + LOBBY".parse::<Perms>(), Ok(Perms::GUEST));
+    // Display output parses back to the same value.
+    let perms = Perms::ADMIN | Perms::MESSAGE_MODS;
+    assert_eq!(perms.to_string().parse::<Perms>(), Ok(perms));
+    // Bits that are not part of any flag are written and parsed as hex.
+    let mut unknown = Perms::LOBBY;
+    unknown.masks[2] |= 0x80;
+    assert_eq!("LOBBY | 0x800000".parse::<Perms>(), Ok(unknown));
+    assert_eq!(unknown.to_string().parse::<Perms>(), Ok(unknown));
+    assert_eq!("0x3 - 0x1".parse::<Perms>(), Ok(Perms::from_index(1)));
+    
+    let err = "ADMIN | LOBY".parse::<Perms>().unwrap_err();
+    assert_eq!(err.kind(), ParseFlagsErrorKind::UnknownFlag);
+    assert_eq!(err.token(), "LOBY");
+    assert_eq!(err.offset(), 8);
+    assert_eq!(err.to_string(), "unknown flag `LOBY` at offset 8");
+    let err = "ADMIN LOBBY".parse::<Perms>().unwrap_err();
+    assert_eq!((err.kind(), err.token(), err.offset()), (ParseFlagsErrorKind::ExpectedOperator, "LOBBY", 6));
+    let err = "ADMIN | - LOBBY".parse::<Perms>().unwrap_err();
+    assert_eq!((err.kind(), err.token(), err.offset()), (ParseFlagsErrorKind::ExpectedFlag, "-", 8));
+    let err = "ADMIN |".parse::<Perms>().unwrap_err();
+    assert_eq!((err.kind(), err.token(), err.offset()), (ParseFlagsErrorKind::ExpectedFlag, "", 7));
+    // Hex bits must fit in the flags type.
+    let err = "LOBBY | 0x1000000000000".parse::<Perms>().unwrap_err();
+    assert_eq!((err.kind(), err.token(), err.offset()), (ParseFlagsErrorKind::UnknownFlag, "0x1000000000000", 8));
+    let err = "ADMIN & LOBBY".parse::<Perms>().unwrap_err();
+    assert_eq!((err.kind(), err.token(), err.offset()), (ParseFlagsErrorKind::InvalidCharacter, "&", 6));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use serde_json::{from_str, to_string};
+    let perms = Perms::GUEST | Perms::BAN_USER;
+    assert_eq!(to_string(&perms).unwrap(), r#"["BAN_USER","LOBBY","MESSAGE_MODS"]"#);
+    assert_eq!(from_str::<Perms>(r#"["BAN_USER","LOBBY","MESSAGE_MODS"]"#).unwrap(), perms);
+    // Group names are accepted.
+    assert_eq!(from_str::<Perms>(r#"["GUEST","BAN_USER"]"#).unwrap(), perms);
+    assert_eq!(from_str::<Perms>("[]").unwrap(), Perms::NONE);
+    assert!(from_str::<Perms>(r#"["GUEST","LOBY"]"#).unwrap_err().to_string().contains("unknown flag `LOBY`"));
+    // Unknown bits are written as hex, and are an error by default.
+    let mut unknown = Perms::LOBBY;
+    unknown.masks[2] |= 0x80;
+    assert_eq!(to_string(&unknown).unwrap(), r#"["LOBBY","0x800000"]"#);
+    assert!(from_str::<Perms>(r#"["LOBBY","0x800000"]"#).unwrap_err().to_string().contains("unknown bits 0x800000"));
+    assert!(from_str::<Perms>(r#"["0x1000000"]"#).is_err());
+    
+    flags!(
+        #[vexillo(serde(repr = bytes, unknown = ignore))]
+        struct Bytes([u8]);
+        const {
+            A
+            B
+            C
+        }
+    );
+    assert_eq!(to_string(&(Bytes::A | Bytes::C)).unwrap(), "[5]");
+    assert_eq!(from_str::<Bytes>("[5]").unwrap(), Bytes::A | Bytes::C);
+    assert_eq!(from_str::<Bytes>("[255]").unwrap(), Bytes::ALL);
+    assert!(from_str::<Bytes>("[1, 0]").is_err());
+    
+    flags!(
+        #[vexillo(serde(repr = masks, unknown = preserve))]
+        struct Masks([u8]);
+        const {
+            A
+            B
+            C
+        }
+    );
+    assert_eq!(to_string(&Masks::B).unwrap(), "2");
+    assert_eq!(from_str::<Masks>("255").unwrap().masks, [255]);
+    flags!(
+        #[vexillo(serde(repr = names, unknown = ignore))]
+        struct Names([u8]);
+        const {
+            A
+            B
+        }
+    );
+    assert_eq!(from_str::<Names>(r#"["A","C","0xff"]"#).unwrap(), Names::ALL);
+    // Multiple masks are written as a sequence, starting with the lowest.
+    flags!(
+        #[vexillo(serde(repr = masks))]
+        struct Wide([u8]);
+        const {
+            F0 F1 F2 F3 F4 F5 F6 F7 F8
+        }
+    );
+    assert_eq!(to_string(&(Wide::F0 | Wide::F8)).unwrap(), "[1,1]");
+    assert_eq!(from_str::<Wide>("[1,1]").unwrap(), Wide::F0 | Wide::F8);
+    assert!(from_str::<Wide>("[1,2]").is_err());
+}
+
+#[test]
+fn test_pinned_indices() {
+    flags!(
+        struct Pinned([u8]);
+        const {
+            A = 3
+            B
+            C = 0
+            GROUP: [
+                D
+                E = 12
+            ]
+        }
+    );
+    assert_eq!(Pinned::C.masks, [0b0001, 0]);
+    assert_eq!(Pinned::B.masks, [0b0010, 0]);
+    assert_eq!(Pinned::D.masks, [0b0100, 0]);
+    assert_eq!(Pinned::A.masks, [0b1000, 0]);
+    assert_eq!(Pinned::E.masks, [0, 0b10000]);
+    assert_eq!(Pinned::GROUP, Pinned::D | Pinned::E);
+    assert_eq!(Pinned::ALL.masks, [0b1111, 0b10000]);
+    assert_eq!(Pinned::SINGLE_FLAG_COUNT, 5);
+    assert_eq!(Pinned::INDEX_SPAN, 13);
+    assert_eq!(Pinned::BITS, 16);
+    assert_eq!(Pinned::MASK_COUNT, 2);
+    assert_eq!(Pinned::USED_BITS, 5);
+    assert_eq!(Pinned::UNUSED_BITS, 11);
+    // Single flags are ordered by bit index.
+    let names = Pinned::TABLES.singles().rows.iter().map(|row| row.name).collect::<Vec<_>>();
+    assert_eq!(names, ["C", "B", "D", "A", "E"]);
+    assert_eq!(Pinned::TABLES.find_index(12).map(|row| row.name), Some("E"));
+    assert!(Pinned::TABLES.find_index(4).is_none());
+    assert_eq!(Pinned::ALL.iter_names().collect::<Vec<_>>(), names);
+    assert_eq!(Pinned::ALL.iter_indices().collect::<Vec<_>>(), [0, 1, 2, 3, 12]);
+    assert_eq!(Pinned::ALL.to_string(), "C | B | D | A | E");
+    // Gaps are never set by bitwise logic.
+    assert_eq!(Pinned::NONE.not(), Pinned::ALL);
+    assert_eq!(Pinned::A.nor(Pinned::B), Pinned::ALL - Pinned::A - Pinned::B);
+    assert_eq!(Pinned::A.nand(Pinned::A), Pinned::ALL - Pinned::A);
+    assert_eq!(Pinned::A.imply(Pinned::NONE), Pinned::ALL - Pinned::A);
+    assert_eq!(Pinned::A.xnor(Pinned::B), Pinned::ALL - Pinned::A - Pinned::B);
+    let mut gap = Pinned::NONE;
+    gap.set(5, true);
+    assert!(!gap.is_valid());
+    assert!(Pinned::ALL.is_valid());
+    assert_eq!(Pinned::A.leading_zeros(), 9);
+    assert_eq!(Pinned::E.leading_zeros(), 0);
+    assert_eq!(Pinned::NONE.leading_zeros(), 13);
+    assert_eq!(Pinned::E.leading_ones(), 1);
+    assert_eq!(Pinned::E.trailing_zeros(), 12);
+    assert_eq!(Pinned::NONE.trailing_zeros(), 13);
+    assert_eq!((Pinned::C | Pinned::B).trailing_ones(), 2);
+    let decomposed = Pinned::GROUP.decompose();
+    assert_eq!(decomposed.len(), 13);
+    assert!(decomposed[2] && decomposed[12]);
+    assert_eq!(Pinned::compose(&decomposed), Pinned::GROUP);
+}
+
+#[test]
+fn test_reserved_bits() {
+    flags!(
+        struct Reserved([u8]);
+        const {
+            A
+            reserved 2
+            B
+            _ = 6
+            _
+            C: [
+                D
+                reserved 1
+                E
+            ]
+            // `reserved` is only a keyword when it is followed by a count.
+            #[allow(non_upper_case_globals)]
+            reserved
+        }
+    );
+    let indices = |flags: Reserved| flags.iter_indices().collect::<Vec<_>>();
+    assert_eq!(indices(Reserved::ALL), [0, 3, 5, 8, 9]);
+    assert_eq!(indices(Reserved::C), [5, 8]);
+    assert_eq!(indices(Reserved::reserved), [9]);
+    assert_eq!(Reserved::SINGLE_FLAG_COUNT, 5);
+    assert_eq!(Reserved::INDEX_SPAN, 10);
+    assert_eq!(Reserved::MASK_COUNT, 2);
+    // Reserved bits are not valid, and bitwise logic never sets them.
+    let mut flags = Reserved::NONE;
+    flags.set(6, true);
+    assert!(!flags.is_valid());
+    assert_eq!(Reserved::NONE.not(), Reserved::ALL);
+    assert_eq!(Reserved::A.xnor(Reserved::B), Reserved::ALL - Reserved::A - Reserved::B);
+    assert!(Reserved::A.xnor(Reserved::B).is_valid());
+    
+    flags!(
+        struct Gap(pub u8);
+        pub const {
+            A = 0
+            reserved 1
+            B = 3
+        }
+    );
+    assert_eq!(Gap::A.xnor(Gap::B), Gap::NONE);
+    assert_eq!(Gap::A.xnor(Gap::A), Gap::ALL);
+    assert!(Gap::NONE.xnor(Gap::NONE).is_valid());
+    
+    flags!(
+        struct Padded([u8]);
+        const {
+            A
+            _ = 15
+        }
+    );
+    // Reserved bits at the end still take up space.
+    assert_eq!(Padded::INDEX_SPAN, 16);
+    assert_eq!(Padded::MASK_COUNT, 2);
+    assert_eq!(Padded::ALL.masks, [1, 0]);
+}
+
+#[test]
+fn test_forward_references() {
+    flags!(
+        struct Forward([u8]);
+        const {
+            // Groups can reference flags and groups that are declared later.
+            #[allow(vexillo::redundant)]
+            EARLY: [+LATE | C]
+            A
+            #[allow(vexillo::duplicate)]
+            LATE: [+MIDDLE - B]
+            MIDDLE: [
+                B
+                C
+                + A
+            ]
+            // Additions are applied before removals.
+            REMOVED: [-A +A | B]
+        }
+    );
+    assert_eq!(Forward::MIDDLE, Forward::A | Forward::B | Forward::C);
+    assert_eq!(Forward::LATE, Forward::A | Forward::C);
+    assert_eq!(Forward::EARLY, Forward::A | Forward::C);
+    assert_eq!(Forward::REMOVED, Forward::B);
+}
+
+#[test]
+fn test_integer_storage() {
+    flags!(
+        #[vexillo(display = groups)]
+        pub struct Small(pub u16);
+        pub const {
+            A
+            B
+            _ = 9
+            AB: [+A | B]
+            C
+        }
+    );
+    assert_eq!(Small::MASK_COUNT, 1);
+    assert_eq!(::core::mem::size_of::<Small>(), 2);
+    assert_eq!(Small::ALL.bits(), 0b111);
+    assert_eq!(Small::AB.bits, 0b11);
+    assert_eq!(Small::from_bits(0b101), Some(Small::A | Small::C));
+    assert_eq!(Small::from_bits(1 << 9), None);
+    assert_eq!(Small::from_bits_truncate(0b1000_0000_0110), Small::B | Small::C);
+    assert!(!Small::from_bits_retain(1 << 9).is_valid());
+    assert_eq!(Small::NONE.not(), Small::ALL);
+    assert_eq!(!Small::A, Small::B | Small::C);
+    assert!(Small::AB.has_all(Small::B) && Small::AB.has_some(Small::ALL));
+    assert_eq!(Small::C.leading_zeros(), 7);
+    assert_eq!(Small::C.trailing_zeros(), 2);
+    assert_eq!(Small::NONE.trailing_zeros(), Small::INDEX_SPAN);
+    assert_eq!(Small::AB.trailing_ones(), 2);
+    assert_eq!(Small::from_le_bytes((Small::AB | Small::C).to_le_bytes()), Small::ALL);
+    assert_eq!(Small::ALL.as_slice(), [0b111]);
+    assert_eq!(Small::ALL.as_bytes(), 0b111u16.to_ne_bytes());
+    assert_eq!(Small::ALL.to_string(), "AB | C");
+    assert_eq!("AB - B".parse::<Small>(), Ok(Small::A));
+    let mut flags = Small::NONE;
+    flags.set(2, true);
+    assert!(!flags.swap(0, true));
+    assert_eq!(flags.iter_names().collect::<Vec<_>>(), ["A", "C"]);
+    // The trait works the same for both kinds of storage.
+    fn first_name<T: vexillo::Flags>(flags: T) -> Option<&'static str> {
+        flags.iter_names().next()
+    }
+    assert_eq!(first_name(Small::C), Some("C"));
+}
+
+#[test]
+fn test_atomic() {
+    use std::sync::atomic::Ordering;
+    
+    flags!(
+        #[vexillo(atomic)]
+        pub struct Wide(pub [u8]);
+        pub const {
+            A
+            B
+            C = 12
+            AC: [+A | C]
+        }
+    );
+    static SHARED: AtomicWide = AtomicWide::new(Wide::A);
+    assert_eq!(SHARED.fetch_add(Wide::C, Ordering::Relaxed), Wide::A);
+    assert_eq!(SHARED.load(Ordering::Relaxed), Wide::AC);
+    assert!(SHARED.has_all(Wide::AC, Ordering::Relaxed));
+    assert!(!SHARED.has_any(Wide::B, Ordering::Relaxed));
+    assert_eq!(SHARED.fetch_remove(Wide::A, Ordering::Relaxed), Wide::AC);
+    assert_eq!(SHARED.fetch_toggle(Wide::ALL, Ordering::Relaxed), Wide::C);
+    assert_eq!(SHARED.load(Ordering::Relaxed), Wide::A | Wide::B);
+    // A mismatch in the second word restores the first word.
+    assert_eq!(
+        SHARED.compare_exchange(Wide::A | Wide::B | Wide::C, Wide::NONE, Ordering::Relaxed, Ordering::Relaxed),
+        Err(Wide::A | Wide::B),
+    );
+    assert_eq!(SHARED.load(Ordering::Relaxed), Wide::A | Wide::B);
+    assert_eq!(
+        SHARED.compare_exchange(Wide::A | Wide::B, Wide::C, Ordering::Relaxed, Ordering::Relaxed),
+        Ok(Wide::A | Wide::B),
+    );
+    SHARED.store(Wide::B, Ordering::Relaxed);
+    assert_eq!(format!("{SHARED:?}"), "Wide(B)");
+    
+    flags!(
+        #[vexillo(atomic = SharedSmall)]
+        struct Small(u16);
+        const {
+            X
+            Y
+        }
+    );
+    let shared = std::sync::Arc::new(SharedSmall::default());
+    let threads = [Small::X, Small::Y].map(|flag| {
+        let shared = shared.clone();
+        std::thread::spawn(move || {
+            shared.fetch_add(flag, Ordering::AcqRel);
+        })
+    });
+    threads.into_iter().for_each(|thread| thread.join().unwrap());
+    assert_eq!(std::sync::Arc::into_inner(shared).unwrap().into_inner(), Small::ALL);
+}
+
+#[test]
+fn test_flag_map() {
+    flags!(
+        struct Keys([u8]);
+        const {
+            A
+            B = 9
+            _
+            C
+            BC: [+B | C]
+        }
+    );
+    let mut map = FlagMap::<Keys, &str>::new();
+    assert!(map.is_empty());
+    assert_eq!(map.insert(Keys::B, "b"), None);
+    assert_eq!(map.insert(Keys::A, "a"), None);
+    assert_eq!(map.insert(Keys::A, "first"), Some("a"));
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.keys(), Keys::A | Keys::B);
+    assert_eq!(map.get(Keys::C), None);
+    *map.get_mut(Keys::B).unwrap() = "second";
+    assert_eq!(map.iter().collect::<Vec<_>>(), [(Keys::A, &"first"), (Keys::B, &"second")]);
+    assert_eq!(format!("{map:?}"), r#"{"A": "first", "B": "second"}"#);
+    assert_eq!(map.remove(Keys::A), Some("first"));
+    assert!(!map.contains_key(Keys::A));
+    let map = [(Keys::C, 3), (Keys::A, 1)].into_iter().collect::<FlagMap<Keys, i32>>();
+    assert_eq!(map.values().copied().collect::<Vec<_>>(), [1, 3]);
+    assert_eq!(map.clone(), map);
+    // Groups, multiple bits, and bits that are not single flags are not keys, even next to a single flag.
+    for key in [Keys::BC, Keys::A | Keys::C, Keys::from_index(1), Keys::A | Keys::from_index(1), Keys::NONE] {
+        assert!(std::panic::catch_unwind(|| map.get(key)).is_err());
+    }
+}
+
+#[test]
+fn test_flag_enum() {
+    flags!(
+        #[vexillo(flag_enum)]
+        pub struct Perms(pub u16);
+        pub const {
+            BAN_USER
+            KICK = 9
+            _
+            SEND_MESSAGES
+            MODERATE: [+BAN_USER | KICK]
+        }
+    );
+    assert_eq!(PermsFlag::ALL_VARIANTS, [PermsFlag::BanUser, PermsFlag::SendMessages, PermsFlag::Kick]);
+    assert_eq!(PermsFlag::Kick as u16, 9);
+    assert_eq!(PermsFlag::SendMessages.index(), 2);
+    assert_eq!(PermsFlag::SendMessages.name(), "SEND_MESSAGES");
+    assert_eq!(PermsFlag::from_index(1), None);
+    assert_eq!(Perms::from(PermsFlag::Kick), Perms::KICK);
+    assert_eq!(PermsFlag::try_from(Perms::BAN_USER), Ok(PermsFlag::BanUser));
+    // Bit 1 is reserved, so it is not part of any flag.
+    for flags in [Perms::NONE, Perms::MODERATE, Perms::from_index(1), Perms::BAN_USER | Perms::from_index(1)] {
+        assert_eq!(PermsFlag::try_from(flags), Err(vexillo::NotSingleFlagError));
+    }
+    assert_eq!(PermsFlag::BanUser | PermsFlag::Kick, Perms::MODERATE);
+    assert_eq!(PermsFlag::BanUser | PermsFlag::Kick | PermsFlag::SendMessages, Perms::ALL);
+    
+    flags!(
+        #[vexillo(flag_enum = Right)]
+        struct Rights([u8]);
+        const {
+            READ
+            WRITE
+        }
+    );
+    assert_eq!(Rights::WRITE | Right::Read, Rights::ALL);
+    assert_eq!(Right::try_from(Rights::WRITE).map(Right::flags), Ok(Rights::WRITE));
+}
+
+#[test]
+fn test_c_header() {
+    flags!(
+        #[vexillo(c_header)]
+        pub struct ChatPerms(pub [u8]);
+        pub const {
+            SEND
+            BAN_USER = 9
+            MODERATE: [+SEND | BAN_USER]
+        }
+    );
+    let header = ChatPerms::C_HEADER;
+    assert!(header.starts_with("/* Generated by vexillo from `ChatPerms`. Do not edit. */\n#ifndef CHAT_PERMS_H\n"));
+    assert!(header.contains("typedef struct chat_perms_t {\n    uint8_t masks[2];\n} chat_perms_t;\n"));
+    assert!(header.contains("#define CHAT_PERMS_BAN_USER_0 UINT8_C(0x0)\n#define CHAT_PERMS_BAN_USER_1 UINT8_C(0x2)\n"));
+    assert!(header.contains("#define CHAT_PERMS_MODERATE_INIT { { CHAT_PERMS_MODERATE_0, CHAT_PERMS_MODERATE_1 } }\n"));
+    assert!(header.contains("#define CHAT_PERMS_MODERATE_0 UINT8_C(0x1)\n#define CHAT_PERMS_MODERATE_1 UINT8_C(0x2)\n"));
+    assert!(header.contains("static inline bool chat_perms_has_all(chat_perms_t a, chat_perms_t b) {"));
+    assert!(header.ends_with("#endif /* CHAT_PERMS_H */\n"));
+    assert_eq!(std::mem::size_of::<ChatPerms>(), 2);
+    
+    flags!(
+        #[vexillo(c_header = perm)]
+        struct Perm(u32);
+        const {
+            READ
+            WRITE
+        }
+    );
+    assert!(Perm::C_HEADER.contains("typedef struct perm_t {\n    uint32_t bits;\n} perm_t;\n"));
+    assert!(Perm::C_HEADER.contains("#define PERM_WRITE UINT32_C(0x2)\n#define PERM_WRITE_INIT { PERM_WRITE }\n"));
+    assert!(Perm::C_HEADER.contains("    a.bits |= b.bits;\n    return a;\n"));
+}
+
+#[cfg(feature = "proptest")]
+mod test_proptest {
+    use ::proptest::prelude::*;
+    use vexillo::flags;
+    
+    flags!(
+        pub struct Sparse([u8]);
+        pub const {
+            A
+            B = 10
+            _
+            C
+            AB: [+A | B]
+        }
+    );
+    
+    proptest! {
+        #[test]
+        fn any_is_valid(flags in any::<Sparse>()) {
+            prop_assert!(flags.is_valid());
+        }
+        
+        #[test]
+        fn subset_of(flags in vexillo::proptest::subset_of(Sparse::AB | Sparse::from_index(1))) {
+            prop_assert!(Sparse::AB.has_all(flags));
+        }
+        
+        #[test]
+        fn superset_of(flags in vexillo::proptest::superset_of(Sparse::C)) {
+            prop_assert!(flags.is_valid() && flags.has_all(Sparse::C));
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_arbitrary() {
+    use ::arbitrary::{Arbitrary, Unstructured};
+    flags!(
+        struct Sparse([u8]);
+        const {
+            A
+            B = 10
+            _
+            C
+        }
+    );
+    // One byte per value, with a bit for each single flag in bit index order: A, C, B.
+    let mut u = Unstructured::new(&[0xFF, 0b010]);
+    assert_eq!(Sparse::arbitrary(&mut u), Ok(Sparse::ALL));
+    assert_eq!(Sparse::arbitrary(&mut u), Ok(Sparse::C));
+    assert_eq!(Sparse::arbitrary(&mut u), Ok(Sparse::NONE));
+    let mut u = Unstructured::new(&[0b110]);
+    assert_eq!(Sparse::arbitrary(&mut u), Ok(Sparse::B | Sparse::C));
+    let mut u = Unstructured::new(&[0xFF]);
+    assert_eq!(vexillo::arbitrary::subset_of(&mut u, Sparse::A | Sparse::from_index(1)), Ok(Sparse::A));
+    assert_eq!(Sparse::size_hint(0), (1, Some(1)));
+}
+
+#[test]
+fn test_convert_by_name() {
+    use vexillo::Flags as _;
+    flags!(
+        struct Perms([u8]);
+        const {
+            SEND_MESSAGES
+            KICK
+            BAN_USER = 9
+            MANAGE_SERVER
+            MODERATE: [+KICK | BAN_USER]
+        }
+    );
+    flags!(
+        struct ChannelPerms(u16);
+        const {
+            BAN_USER
+            SEND_MESSAGES
+            KICK
+            // A group in the other type is not a counterpart of a single flag.
+            MANAGE_SERVER: [+BAN_USER | KICK]
+        }
+    );
+    let converted = (Perms::MODERATE | Perms::MANAGE_SERVER | Perms::from_index(5)).convert_by_name::<ChannelPerms>();
+    assert_eq!(converted.value, ChannelPerms::BAN_USER | ChannelPerms::KICK);
+    assert_eq!(converted.unmatched, Perms::MANAGE_SERVER | Perms::from_index(5));
+    assert_eq!(converted.unmatched_names().collect::<Vec<_>>(), ["MANAGE_SERVER"]);
+    assert!(!converted.is_complete());
+    
+    let back = ChannelPerms::ALL.convert_by_name::<Perms>();
+    assert_eq!(back.value, Perms::SEND_MESSAGES | Perms::MODERATE);
+    assert!(back.is_complete());
+    
+    const CONVERTED: ConvertedFlags<Perms, ChannelPerms> = convert_by_name!(Perms::ALL, Perms => ChannelPerms);
+    assert_eq!(CONVERTED, Perms::ALL.convert_by_name::<ChannelPerms>());
+    const BACK: ConvertedFlags<ChannelPerms, Perms> = convert_by_name!(ChannelPerms::ALL, ChannelPerms => Perms);
+    assert_eq!(BACK, back);
+}
+
+#[test]
+fn test_lock() {
+    // `SEND_MESSAGES` was added in front of the other flags after the lockfile was created,
+    // and recorded after them with `VEXILLO_UPDATE_LOCK` set.
+    flags!(
+        #[vexillo(lock = "tests/perms.lock")]
+        struct LockedPerms([u8]);
+        const {
+            SEND_MESSAGES
+            BAN_USER
+            KICK
+        }
+    );
+    assert_eq!(LockedPerms::KICK, LockedPerms::from_index(0));
+    assert_eq!(LockedPerms::BAN_USER, LockedPerms::from_index(1));
+    assert_eq!(LockedPerms::SEND_MESSAGES, LockedPerms::from_index(2));
+    let lockfile = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/perms.lock")).unwrap();
+    assert!(lockfile.contains("SEND_MESSAGES = 2"));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_aliases() {
+    flags!(
+        struct ChatPerms([u8]);
+        const {
+            SEND_MESSAGES
+            #[deprecated]
+            SEND_TTS
+            REMOVE_MEMBER
+            MODERATOR: [+REMOVE_MEMBER | SEND_MESSAGES]
+            alias KICK = REMOVE_MEMBER
+            #[deprecated(note = "Use `MODERATOR`.")]
+            alias MOD = MODERATOR
+        }
+    );
+    assert_eq!(ChatPerms::KICK, ChatPerms::REMOVE_MEMBER);
+    assert_eq!(ChatPerms::MOD, ChatPerms::MODERATOR);
+    assert_eq!(ChatPerms::TOTAL_FLAG_COUNT, 4);
+    assert_eq!(ChatPerms::try_find("KICK"), Some(ChatPerms::REMOVE_MEMBER));
+    assert_eq!(ChatPerms::TABLES.find_single("KICK").map(|row| row.name), Some("REMOVE_MEMBER"));
+    assert!(ChatPerms::TABLES.find_group("KICK").is_none());
+    assert_eq!(<ChatPerms as vexillo::Flags>::try_find("MOD"), Some(ChatPerms::MODERATOR));
+    assert_eq!("SEND_MESSAGES | KICK".parse::<ChatPerms>(), Ok(ChatPerms::MODERATOR));
+    assert_eq!(ChatPerms::REMOVE_MEMBER.to_string(), "REMOVE_MEMBER");
+    
+    assert!(ChatPerms::TABLES.find("SEND_TTS").unwrap().is_deprecated());
+    assert!(!ChatPerms::TABLES.find("SEND_MESSAGES").unwrap().is_deprecated());
+    let perms = ChatPerms::ALL;
+    assert_eq!(perms.iter_names().collect::<Vec<_>>(), ["SEND_MESSAGES", "SEND_TTS", "REMOVE_MEMBER"]);
+    assert_eq!(perms.iter_names().skip_deprecated().collect::<Vec<_>>(), ["SEND_MESSAGES", "REMOVE_MEMBER"]);
+    let mut names = perms.iter_names();
+    assert_eq!(names.next(), Some("SEND_MESSAGES"));
+    assert_eq!(names.skip_deprecated().collect::<Vec<_>>(), ["REMOVE_MEMBER"]);
+}
+
+#[test]
+fn test_exclusive() {
+    flags!(
+        struct Post(u8);
+        const {
+            VISIBILITY_PUBLIC
+            VISIBILITY_UNLISTED
+            VISIBILITY_PRIVATE
+            PINNED
+            LOCKED
+            DEFAULT: [+VISIBILITY_PUBLIC]
+            exclusive [VISIBILITY_PUBLIC, VISIBILITY_UNLISTED, VISIBILITY_PRIVATE]
+            exclusive [PINNED, LOCKED]
+        }
+    );
+    assert_eq!(Post::EXCLUSIVE_SETS, [
+        Post::VISIBILITY_PUBLIC | Post::VISIBILITY_UNLISTED | Post::VISIBILITY_PRIVATE,
+        Post::PINNED | Post::LOCKED,
+    ]);
+    assert_eq!(Post::NONE.validate(), Ok(()));
+    assert_eq!((Post::DEFAULT | Post::PINNED).validate(), Ok(()));
+    let conflict = (Post::VISIBILITY_PUBLIC | Post::VISIBILITY_PRIVATE | Post::LOCKED).validate().unwrap_err();
+    assert_eq!(conflict.flags, Post::VISIBILITY_PUBLIC | Post::VISIBILITY_PRIVATE);
+    assert_eq!(conflict.set, Post::EXCLUSIVE_SETS[0]);
+    assert_eq!(conflict.to_string(), "mutually exclusive flags are set: VISIBILITY_PUBLIC | VISIBILITY_PRIVATE");
+    assert!(Post::ALL.validate().is_err());
+    
+    let mut post = Post::DEFAULT | Post::PINNED;
+    post.set_exclusive(Post::VISIBILITY_PRIVATE);
+    assert_eq!(post, Post::VISIBILITY_PRIVATE | Post::PINNED);
+    post.set_exclusive(Post::LOCKED);
+    assert_eq!(post, Post::VISIBILITY_PRIVATE | Post::LOCKED);
+    
+    // The methods can be renamed like other builtins.
+    flags!(
+        struct WidePost([u8]);
+        override {
+            pub validate: check_exclusive
+            pub or_assign: union_assign
+        }
+        const {
+            reserved 9
+            A
+            B
+            C
+            exclusive [A, C]
+        }
+    );
+    const VALUE: WidePost = {
+        let mut value = WidePost::A;
+        value.set_exclusive(WidePost::B);
+        value.set_exclusive(WidePost::C);
+        value
+    };
+    assert_eq!(VALUE, WidePost::B | WidePost::C);
+    assert!(VALUE.check_exclusive().is_ok());
+    assert!((WidePost::A | WidePost::C).check_exclusive().is_err());
+}
+
+#[test]
+fn test_implications() {
+    flags!(
+        struct ModPerms([u8]);
+        const {
+            VIEW_AUDIT_LOG
+            VIEW_MEMBERS
+            KICK
+            BAN_USER
+            SEND_MESSAGES
+            MODERATOR: [+KICK | SEND_MESSAGES]
+            implies BAN_USER => VIEW_AUDIT_LOG | KICK
+            implies KICK => VIEW_MEMBERS
+            implies VIEW_MEMBERS | SEND_MESSAGES => NONE
+        }
+    );
+    assert_eq!(ModPerms::IMPLICATIONS, [
+        (ModPerms::KICK, ModPerms::VIEW_MEMBERS),
+        (ModPerms::BAN_USER, ModPerms::VIEW_AUDIT_LOG | ModPerms::VIEW_MEMBERS | ModPerms::KICK),
+    ]);
+    assert_eq!(ModPerms::BAN_USER.normalize(), ModPerms::BAN_USER | ModPerms::VIEW_AUDIT_LOG | ModPerms::KICK | ModPerms::VIEW_MEMBERS);
+    assert_eq!(ModPerms::MODERATOR.normalize(), ModPerms::MODERATOR | ModPerms::VIEW_MEMBERS);
+    assert!(!ModPerms::BAN_USER.is_normalized());
+    assert!(ModPerms::BAN_USER.normalize().is_normalized());
+    assert!(ModPerms::NONE.is_normalized());
+    assert!(ModPerms::SEND_MESSAGES.is_normalized());
+    assert_eq!(ModPerms::SEND_MESSAGES.with_implied(ModPerms::KICK), ModPerms::MODERATOR | ModPerms::VIEW_MEMBERS);
+    // Only the implications of `flag` are added.
+    assert_eq!(ModPerms::KICK.with_implied(ModPerms::SEND_MESSAGES), ModPerms::MODERATOR);
+    
+    // The methods can be renamed like other builtins.
+    flags!(
+        struct Cyclic(u16);
+        override {
+            pub normalize: with_all_implied
+        }
+        const {
+            READ
+            WRITE
+            implies READ => WRITE
+            implies WRITE => READ
+        }
+    );
+    const NORMALIZED: Cyclic = Cyclic::READ.with_all_implied();
+    assert_eq!(NORMALIZED, Cyclic::ALL);
+    assert_eq!(Cyclic::NONE.with_implied(Cyclic::WRITE), Cyclic::ALL);
+}
+
+#[test]
+fn test_hierarchy() {
+    flags!(
+        struct Roles([u16]);
+        override {
+            pub eq: equals
+        }
+        const {
+            OWNER: [
+                TRANSFER
+                ADMIN: [
+                    MANAGE_SERVER
+                    MOD: [
+                        KICK
+                        USER: [
+                            SEND_MESSAGES
+                            GUEST: [
+                                VIEW
+                            ]
+                        ]
+                    ]
+                ]
+            ]
+            LOOSE
+        }
+    );
+    assert_eq!(Roles::parent_of(Roles::VIEW), Some(Roles::GUEST));
+    assert_eq!(Roles::parent_of(Roles::ADMIN), Some(Roles::OWNER));
+    assert_eq!(Roles::parent_of(Roles::OWNER), None);
+    assert_eq!(Roles::parent_of(Roles::LOOSE), None);
+    assert_eq!(Roles::parent_of(Roles::KICK | Roles::LOOSE), None);
+    assert_eq!(Roles::children_of(Roles::OWNER), [Roles::TRANSFER, Roles::ADMIN]);
+    assert_eq!(Roles::children_of(Roles::MOD), [Roles::KICK, Roles::USER]);
+    // `GUEST` has the same value as `VIEW`, but only groups have children.
+    assert_eq!(Roles::GUEST, Roles::VIEW);
+    assert_eq!(Roles::children_of(Roles::GUEST), [Roles::VIEW]);
+    assert!(Roles::children_of(Roles::KICK).is_empty());
+    assert_eq!(
+        Roles::ancestors_of(Roles::SEND_MESSAGES),
+        [Roles::USER, Roles::MOD, Roles::ADMIN, Roles::OWNER],
+    );
+    assert!(Roles::ancestors_of(Roles::LOOSE).is_empty());
+    // Single flags are found before groups with the same value.
+    assert_eq!(Roles::depth_of(Roles::GUEST), Some(5));
+    assert_eq!(Roles::depth_of(Roles::OWNER), Some(0));
+    assert_eq!(Roles::depth_of(Roles::NONE), None);
+    assert_eq!(Roles::row_of(Roles::GUEST).map(|row| row.name), Some("VIEW"));
+    let row = Roles::row_of(Roles::MOD).unwrap();
+    assert_eq!((row.name, row.parent(), row.depth()), ("MOD", Some(Roles::ADMIN), 2));
+    // The `*_by_name` versions find the flag that was named.
+    assert_eq!(Roles::depth_of_by_name("GUEST"), Some(4));
+    assert_eq!(Roles::parent_of_by_name("GUEST"), Some(Roles::USER));
+    assert_eq!(Roles::children_of_by_name("GUEST"), [Roles::VIEW]);
+    assert!(Roles::children_of_by_name("VIEW").is_empty());
+    assert_eq!(Roles::ancestors_of_by_name("USER"), [Roles::MOD, Roles::ADMIN, Roles::OWNER]);
+    assert_eq!(Roles::parent_of_by_name("MISSING"), None);
+    assert_eq!(Roles::depth_of_by_name("MISSING"), None);
+    
+    // Outer groups have the flags of the groups declared in them.
+    const KICK_FROM: Option<Roles> = Roles::parent_of(Roles::KICK);
+    assert_eq!(KICK_FROM, Some(Roles::MOD));
+    assert!(Roles::ancestors_of(Roles::KICK).iter().all(|group| group.has_all(Roles::KICK)));
+    assert!(!Roles::USER.has_all(Roles::KICK));
+    
+    // Groups with the same value are told apart by name.
+    flags!(
+        struct Chain;
+        const {
+            #[allow(vexillo::duplicate)]
+            OWNER: [
+                #[allow(vexillo::duplicate)]
+                ADMIN: [
+                    GUEST: [
+                        VIEW
+                    ]
+                ]
+            ]
+        }
+    );
+    assert!(Chain::OWNER == Chain::ADMIN && Chain::ADMIN == Chain::GUEST && Chain::GUEST == Chain::VIEW);
+    assert_eq!(Chain::depth_of(Chain::ADMIN), Some(3));
+    assert_eq!(Chain::depth_of_by_name("ADMIN"), Some(1));
+    assert_eq!(Chain::depth_of_by_name("GUEST"), Some(2));
+    assert_eq!(Chain::depth_of_by_name("OWNER"), Some(0));
+    assert_eq!(Chain::parent_of_by_name("ADMIN"), Some(Chain::OWNER));
+    assert_eq!(Chain::parent_of_by_name("OWNER"), None);
+    assert_eq!(Chain::ancestors_of_by_name("GUEST").len(), 2);
+}
+
 #[test]
 fn test_ops() {
     let flag: Flags = Flags::FLAG0 | Flags::FLAG1;